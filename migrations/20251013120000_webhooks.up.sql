@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS "webhook" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"url" TEXT NOT NULL,
+	"secret" CHARACTER VARYING(255) NOT NULL,
+	"events" TEXT[] NOT NULL,
+	"active" BOOLEAN NOT NULL DEFAULT TRUE,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"updated_at" TIMESTAMPTZ
+);
+
+CREATE TABLE IF NOT EXISTS "webhook_delivery" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"webhook_id" BIGINT NOT NULL REFERENCES "webhook" ON UPDATE CASCADE ON DELETE CASCADE,
+	"event" CHARACTER VARYING(100) NOT NULL,
+	"payload" TEXT NOT NULL,
+	"attempt" INTEGER NOT NULL,
+	"status_code" INTEGER,
+	"success" BOOLEAN NOT NULL,
+	"error" TEXT,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "webhook_delivery_webhook_id_idx" ON "webhook_delivery" ("webhook_id", "created_at");
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS "request_open_employee_id_idx";
+DROP INDEX IF EXISTS "request_service_id_idx";
+DROP INDEX IF EXISTS "request_desired_at_idx";
+DROP INDEX IF EXISTS "request_closed_at_idx";
+DROP INDEX IF EXISTS "request_created_at_idx";
@@ -0,0 +1,6 @@
+-- Add migration script here
+CREATE INDEX IF NOT EXISTS "request_created_at_idx" ON "request" ("created_at");
+CREATE INDEX IF NOT EXISTS "request_closed_at_idx" ON "request" ("closed_at") WHERE "closed_at" IS NOT NULL;
+CREATE INDEX IF NOT EXISTS "request_desired_at_idx" ON "request" ("desired_at");
+CREATE INDEX IF NOT EXISTS "request_service_id_idx" ON "request" ("service_id");
+CREATE INDEX IF NOT EXISTS "request_open_employee_id_idx" ON "request" ("employee_id") WHERE "closed_at" IS NULL;
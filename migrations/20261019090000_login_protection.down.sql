@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "rate_limit";
+
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "locked_at";
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "failed_logins";
@@ -0,0 +1,11 @@
+-- Add migration script here
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "failed_logins" INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "locked_at" TIMESTAMPTZ;
+
+-- Общие для всех экземпляров счётчики ограничения частоты запросов. Потеря при сбое
+-- не страшна, поэтому таблица не пишется в WAL
+CREATE UNLOGGED TABLE IF NOT EXISTS "rate_limit" (
+	"key" TEXT NOT NULL PRIMARY KEY,
+	"tokens" DOUBLE PRECISION NOT NULL,
+	"updated_at" TIMESTAMPTZ NOT NULL
+);
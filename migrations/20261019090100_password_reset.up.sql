@@ -0,0 +1,13 @@
+-- Add migration script here
+-- Одноразовые токены восстановления пароля. Хранится только SHA-256 токена,
+-- сам токен уходит сотруднику письмом
+CREATE TABLE IF NOT EXISTS "password_reset" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"employee_id" BIGINT NOT NULL REFERENCES "employee" ("id") ON DELETE CASCADE,
+	"token_hash" TEXT NOT NULL UNIQUE,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"used_at" TIMESTAMPTZ,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "password_reset_employee_id_idx" ON "password_reset" ("employee_id");
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "refresh_token";
+DROP TABLE IF EXISTS "session";
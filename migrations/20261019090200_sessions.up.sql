@@ -0,0 +1,24 @@
+-- Add migration script here
+-- Сессия создаётся при входе и живёт, пока обновляются токены
+CREATE TABLE IF NOT EXISTS "session" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"employee_id" BIGINT NOT NULL REFERENCES "employee" ("id") ON DELETE CASCADE,
+	"user_agent" TEXT,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"last_used_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"revoked_at" TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS "session_employee_id_idx" ON "session" ("employee_id");
+
+-- Все выданные refresh токены сессии. Использованные остаются, чтобы распознать
+-- повторное предъявление украденного токена
+CREATE TABLE IF NOT EXISTS "refresh_token" (
+	"jti" TEXT NOT NULL PRIMARY KEY,
+	"session_id" BIGINT NOT NULL REFERENCES "session" ("id") ON DELETE CASCADE,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"used_at" TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS "refresh_token_session_id_idx" ON "refresh_token" ("session_id");
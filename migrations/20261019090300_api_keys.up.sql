@@ -0,0 +1,15 @@
+-- Add migration script here
+-- Ключи доступа для интеграций. Хранится только SHA-256 ключа, сам ключ
+-- показывается один раз при создании
+CREATE TABLE IF NOT EXISTS "api_key" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"name" TEXT NOT NULL,
+	"prefix" TEXT NOT NULL,
+	"key_hash" TEXT NOT NULL UNIQUE,
+	"permissions" TEXT[] NOT NULL,
+	"created_by" BIGINT REFERENCES "employee" ("id") ON DELETE SET NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"expires_at" TIMESTAMPTZ,
+	"last_used_at" TIMESTAMPTZ,
+	"revoked_at" TIMESTAMPTZ
+);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "recovery_code";
+
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "totp_last_step";
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "totp_enabled_at";
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "totp_secret";
@@ -0,0 +1,15 @@
+-- Add migration script here
+-- Секрет TOTP в base32. Пока `totp_enabled_at` пуст, секрет только выдан и ждёт
+-- подтверждения кодом. `totp_last_step` не даёт повторно использовать код
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "totp_secret" TEXT;
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "totp_enabled_at" TIMESTAMPTZ;
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "totp_last_step" BIGINT;
+
+-- Одноразовые коды восстановления, хранится только SHA-256
+CREATE TABLE IF NOT EXISTS "recovery_code" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"employee_id" BIGINT NOT NULL REFERENCES "employee" ("id") ON DELETE CASCADE,
+	"code_hash" TEXT NOT NULL,
+	"used_at" TIMESTAMPTZ,
+	UNIQUE ("employee_id", "code_hash")
+);
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE "employee" DROP COLUMN IF EXISTS "locale";
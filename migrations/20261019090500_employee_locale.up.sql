@@ -0,0 +1,3 @@
+-- Add migration script here
+-- Язык сообщений, выбранный сотрудником. NULL - по `Accept-Language`
+ALTER TABLE "employee" ADD COLUMN IF NOT EXISTS "locale" VARCHAR(8);
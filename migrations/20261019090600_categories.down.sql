@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS "service_category_id_idx";
+ALTER TABLE "service" DROP COLUMN IF EXISTS "category_id";
+
+DROP TABLE IF EXISTS "category";
@@ -0,0 +1,15 @@
+-- Add migration script here
+-- Категории каталога услуг. Вложенность через `parent_id`, циклы запрещает приложение
+CREATE TABLE IF NOT EXISTS "category" (
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"name" TEXT NOT NULL,
+	"parent_id" BIGINT REFERENCES "category" ("id") ON DELETE RESTRICT,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"updated_at" TIMESTAMPTZ
+);
+
+-- Названия уникальны среди соседей, корневые категории считаются соседями
+CREATE UNIQUE INDEX IF NOT EXISTS "category_parent_name_idx" ON "category" (COALESCE("parent_id", 0), "name");
+
+ALTER TABLE "service" ADD COLUMN IF NOT EXISTS "category_id" BIGINT REFERENCES "category" ("id") ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS "service_category_id_idx" ON "service" ("category_id");
@@ -0,0 +1,9 @@
+-- Add down migration script here
+ALTER TABLE "service"
+	DROP CONSTRAINT IF EXISTS "service_price_currency_check",
+	DROP COLUMN IF EXISTS "active",
+	DROP COLUMN IF EXISTS "skill_level",
+	DROP COLUMN IF EXISTS "duration_minutes",
+	DROP COLUMN IF EXISTS "currency",
+	DROP COLUMN IF EXISTS "base_price",
+	DROP COLUMN IF EXISTS "description";
@@ -0,0 +1,11 @@
+-- Add migration script here
+-- Описание, цена, длительность и уровень исполнителя услуги. Цена хранится в
+-- минимальных единицах валюты (копейках, центах), чтобы не терять точность
+ALTER TABLE "service"
+	ADD COLUMN IF NOT EXISTS "description" TEXT,
+	ADD COLUMN IF NOT EXISTS "base_price" BIGINT CHECK ("base_price" >= 0),
+	ADD COLUMN IF NOT EXISTS "currency" VARCHAR(3),
+	ADD COLUMN IF NOT EXISTS "duration_minutes" INTEGER CHECK ("duration_minutes" > 0),
+	ADD COLUMN IF NOT EXISTS "skill_level" VARCHAR(16),
+	ADD COLUMN IF NOT EXISTS "active" BOOLEAN NOT NULL DEFAULT TRUE,
+	ADD CONSTRAINT "service_price_currency_check" CHECK (("base_price" IS NULL) = ("currency" IS NULL));
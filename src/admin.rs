@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use crate::cli::Command;
+use crate::config::Config;
+use crate::features::employee::{logic::Logic, repo::Repo};
+use crate::models::{dao, dto};
+use crate::{db, logger, password};
+
+/// Выполняет административные подкоманды напрямую через `employee::Logic`,
+/// минуя HTTP API.
+pub async fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    let config = Config::build()?;
+    logger::init_dev_logger();
+    password::configure(&config.security)?;
+
+    let pool = db::connect(&config.database)?;
+    let logic = Logic::new(Arc::new(Repo::new(Arc::new(pool))));
+
+    match command {
+        Command::CreateSuperadmin {
+            email,
+            name,
+            last_name,
+            middle_name,
+            password,
+        } => {
+            let password = password_or_prompt(password)?;
+            let payload = dto::Employee {
+                id: None,
+                name: Some(name),
+                last_name: Some(last_name),
+                middle_name,
+                email: Some(email.clone()),
+                password: Some(password),
+                role: Some(dao::Role::Superadmin.code().to_string()),
+                services: None,
+                active: None,
+                created_at: None,
+                updated_at: None,
+            };
+            logic.create_employee(payload).await.map_err(message)?;
+            println!("Superadmin {email} created");
+        }
+        Command::ResetPassword { email, password } => {
+            let password = password_or_prompt(password)?;
+            logic
+                .reset_password(&email, &password)
+                .await
+                .map_err(message)?;
+            println!("Password of {email} updated");
+        }
+        Command::DeactivateEmployee { email } => {
+            logic.deactivate_employee(&email).await.map_err(message)?;
+            println!("Employee {email} deactivated");
+        }
+        Command::UnlockEmployee { email } => {
+            logic
+                .unlock_employee_by_email(&email)
+                .await
+                .map_err(message)?;
+            println!("Employee {email} unlocked");
+        }
+        Command::ListEmployees => {
+            for e in logic.get_employees().await.map_err(message)? {
+                println!(
+                    "{}\t{}\t{} {}\t{}\t{}",
+                    e.id.unwrap_or_default(),
+                    e.email.unwrap_or_default(),
+                    e.last_name.unwrap_or_default(),
+                    e.name.unwrap_or_default(),
+                    e.role.unwrap_or_default(),
+                    if e.active == Some(true) {
+                        "active"
+                    } else {
+                        "inactive"
+                    },
+                );
+            }
+        }
+        Command::Serve | Command::Migrate { .. } => unreachable!("handled in main"),
+    }
+    Ok(())
+}
+
+fn message(err: dto::Error) -> Box<dyn Error> {
+    err.message().into()
+}
+
+fn password_or_prompt(password: Option<String>) -> Result<String, Box<dyn Error>> {
+    if let Some(password) = password {
+        return Ok(password);
+    }
+
+    eprint!("Password: ");
+    io::stderr().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
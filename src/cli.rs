@@ -0,0 +1,68 @@
+use clap::{Parser, Subcommand};
+
+/// Аргументы командной строки. Без подкоманды запускается сервер.
+#[derive(Debug, Parser)]
+#[command(version, about = "MDS backend")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Запуск HTTP сервера
+    Serve,
+    /// Управление миграциями базы данных
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Создать сотрудника с ролью суперадмина
+    CreateSuperadmin {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        last_name: String,
+        #[arg(long)]
+        middle_name: Option<String>,
+        /// Если не указан, читается из стандартного ввода
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Задать сотруднику новый пароль, снять блокировку и завершить его сессии
+    ResetPassword {
+        #[arg(long)]
+        email: String,
+        /// Если не указан, читается из стандартного ввода
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Отключить учётную запись сотрудника
+    DeactivateEmployee {
+        #[arg(long)]
+        email: String,
+    },
+    /// Снять блокировку после неудачных входов
+    UnlockEmployee {
+        #[arg(long)]
+        email: String,
+    },
+    /// Вывести список сотрудников
+    ListEmployees,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateAction {
+    /// Применить все новые миграции
+    Up,
+    /// Откатить последние миграции
+    Down {
+        /// Сколько миграций откатить
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// Показать применённые и ожидающие миграции
+    Status,
+}
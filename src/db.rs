@@ -1,16 +1,87 @@
-use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
+use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, Migrator};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Pool, Postgres};
 use std::error::Error;
+use std::str::FromStr;
 
-pub fn connect(url: &String) -> Result<Pool<Postgres>, Box<dyn Error>> {
-    // TODO Установить значение в 20 перед релизом (начальная точка 20-100)
-    // Рассчитывать 2-4 * кол-во ядер CPU
-    // Брать во внимание 1-2 подключения для клиента/воркера
-    tracing::info!("Connection to postgres");
-    match PgPoolOptions::new().max_connections(10).connect_lazy(&url) {
-        Ok(v) => Ok(v),
-        Err(e) => {
-            tracing::error!("Database error: {}", e.to_string());
-            Err(e.to_string().into())
+use crate::config::DatabaseConfig;
+
+pub fn connect(config: &DatabaseConfig) -> Result<Pool<Postgres>, Box<dyn Error>> {
+    tracing::info!(
+        max_connections = config.max_connections,
+        min_connections = config.min_connections,
+        acquire_timeout = ?config.acquire_timeout,
+        idle_timeout = ?config.idle_timeout,
+        max_lifetime = ?config.max_lifetime,
+        statement_timeout = ?config.statement_timeout,
+        "Connection to postgres"
+    );
+    connect_url(config, &config.url)
+}
+
+/// Пул реплики с теми же настройками, что и у основного. `None`, если реплика не задана.
+pub fn connect_read(config: &DatabaseConfig) -> Result<Option<Pool<Postgres>>, Box<dyn Error>> {
+    match &config.read_url {
+        Some(url) => {
+            tracing::info!("Connection to postgres read replica");
+            connect_url(config, url).map(Some)
         }
+        None => Ok(None),
+    }
+}
+
+fn connect_url(config: &DatabaseConfig, url: &str) -> Result<Pool<Postgres>, Box<dyn Error>> {
+    let mut options = PgConnectOptions::from_str(url).map_err(|e| {
+        tracing::error!("Database error: {}", e.to_string());
+        e.to_string()
+    })?;
+    if let Some(timeout) = config.statement_timeout {
+        options = options.options([("statement_timeout", timeout.as_millis().to_string())]);
     }
+
+    Ok(PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+        .connect_lazy_with(options))
+}
+
+/// Миграции из `./migrations`, встроенные в бинарник.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Применяет все ещё не применённые миграции.
+pub async fn migrate_up(pool: &Pool<Postgres>) -> Result<(), MigrateError> {
+    tracing::info!("Applying pending migrations");
+    MIGRATOR.run(pool).await.map_err(|err| {
+        tracing::error!("Migration error: {err}");
+        err
+    })
+}
+
+/// Откатывает `steps` последних применённых миграций.
+pub async fn migrate_down(pool: &Pool<Postgres>, steps: usize) -> Result<(), MigrateError> {
+    let mut applied: Vec<i64> = migrate_applied(pool)
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+    applied.sort_unstable_by(|a, b| b.cmp(a));
+    // `undo` откатывает всё, что новее `target`, поэтому целью становится
+    // первая миграция, которая должна остаться.
+    let target = applied.get(steps).copied().unwrap_or(0);
+
+    tracing::info!("Reverting migrations newer than {target}");
+    MIGRATOR.undo(pool, target).await.map_err(|err| {
+        tracing::error!("Migration error: {err}");
+        err
+    })
+}
+
+/// Миграции, уже применённые к базе.
+pub async fn migrate_applied(pool: &Pool<Postgres>) -> Result<Vec<AppliedMigration>, MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    conn.list_applied_migrations().await
 }
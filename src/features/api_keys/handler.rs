@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::features::auth::AuthEmployee;
+use crate::models::dao::Role;
+use crate::models::dto::ApiKey;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn create_api_key(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<ApiKey>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("ApiKey handler: create_api_key", name = ?payload.name);
+        async {
+            let result = match auth.require(&[Role::Superadmin]) {
+                Ok(()) => handler.logic.create(payload, auth.id).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(result) => (StatusCode::CREATED, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to create API key: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_api_keys(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("ApiKey handler: get_api_keys");
+        async {
+            let result = match auth.require(&[Role::Superadmin]) {
+                Ok(()) => handler.logic.get_all().await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get API keys");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn revoke_api_key(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("ApiKey handler: revoke_api_key with ", id);
+        async {
+            let result = match auth.require(&[Role::Superadmin]) {
+                Ok(()) => handler.logic.revoke(id).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => {
+                    tracing::info!("API key {id} revoked by {}", auth.id);
+                    (StatusCode::OK, Json(json!({"id": id})))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to revoke API key");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use rand::Rng;
+
+use super::repo::Repo;
+use crate::features::auth::token::{API_KEY_PREFIX, digest};
+use crate::models::dao;
+use crate::models::dto::{ApiKey, Error};
+
+/// Сколько символов ключа сохраняется открыто, чтобы узнать его в списке.
+const VISIBLE_PREFIX_LEN: usize = API_KEY_PREFIX.len() + 8;
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    /// Создаёт ключ и возвращает его в `key`. В базе остаётся только хеш, повторно
+    /// ключ получить нельзя.
+    #[tracing::instrument(name = "ApiKey logic: create", skip_all)]
+    pub async fn create(&self, payload: ApiKey, created_by: i64) -> Result<ApiKey, Error> {
+        tracing::debug!("ApiKey logic: Creating API key");
+        let name = match payload.name {
+            Some(name) if !name.trim().is_empty() => name,
+            _ => {
+                return Err(Error::BadRequest(
+                    "Field 'name' can't be empty.".to_string(),
+                ));
+            }
+        };
+        let permissions = Self::validate_permissions(payload.permissions)?;
+        if payload.expires_at.is_some_and(|at| at <= Utc::now()) {
+            return Err(Error::BadRequest(
+                "Field 'expires_at' must be in the future.".to_string(),
+            ));
+        }
+
+        let key = format!(
+            "{API_KEY_PREFIX}{}",
+            hex::encode(rand::rng().random::<[u8; 32]>())
+        );
+        let created = self
+            .repo
+            .create(
+                name,
+                key[..VISIBLE_PREFIX_LEN].to_string(),
+                digest(&key),
+                permissions,
+                created_by,
+                payload.expires_at,
+            )
+            .await
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!("API key {} created by employee {created_by}", created.id);
+        Ok(ApiKey {
+            key: Some(key),
+            ..dao::ApiKey::to_dto(created)
+        })
+    }
+
+    #[tracing::instrument(name = "ApiKey logic: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<ApiKey>, Error> {
+        tracing::debug!("ApiKey logic: Getting all API keys");
+        self.repo
+            .get_all()
+            .await
+            .map(|v| v.into_iter().map(dao::ApiKey::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "ApiKey logic: revoke", skip_all)]
+    pub async fn revoke(&self, id: i64) -> Result<(), Error> {
+        tracing::debug!("ApiKey logic: Revoking API key");
+        match self.repo.revoke(id).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::NotFound(format!(
+                "Active API key with id: {} not found",
+                id
+            ))),
+            Err(_) => Err(Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+
+    fn validate_permissions(permissions: Option<Vec<String>>) -> Result<Vec<String>, Error> {
+        let permissions = permissions.unwrap_or_default();
+        if permissions.is_empty() {
+            return Err(Error::BadRequest(
+                "Field 'permissions' can't be empty.".to_string(),
+            ));
+        }
+
+        let mut codes = Vec::<String>::new();
+        for permission in permissions {
+            let code = dao::Permission::from(&permission)?.code().to_string();
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        Ok(codes)
+    }
+}
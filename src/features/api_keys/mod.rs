@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::features::api_keys::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+/// Управление API ключами интеграций, только для суперадмина. Сами ключи
+/// проверяет `features::auth::Principal`.
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/api-keys", post(Handler::create_api_key))
+        .route("/api-keys", get(Handler::get_api_keys))
+        .route("/api-keys/{id}", delete(Handler::revoke_api_key))
+        .with_state(handler)
+}
@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ApiKey, ErrorResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create_api_key, get_api_keys, revoke_api_key),
+    components(schemas(ApiKey, ErrorResponse)),
+    tags((name = "api-keys", description = "Ключи доступа интеграций"))
+)]
+pub struct ApiDoc;
+
+/// Создание API ключа
+///
+/// Обязательны `name` и `permissions`, например `["requests:create"]`. Ключ возвращается
+/// в `key` только в этом ответе и передаётся как `Authorization: Bearer <key>`.
+/// Доступно только суперадмину.
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    tag = "api-keys",
+    request_body = ApiKey,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Ключ создан", body = ApiKey),
+        (status = 400, description = "Пустое имя, неизвестное право или срок в прошлом", body = ErrorResponse),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse)
+    )
+)]
+fn create_api_key() {}
+
+/// Список API ключей
+///
+/// Сами ключи не возвращаются, только `prefix`. Доступно только суперадмину.
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    tag = "api-keys",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Список ключей, включая отозванные", body = Vec<ApiKey>),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse)
+    )
+)]
+fn get_api_keys() {}
+
+/// Отзыв API ключа
+///
+/// Доступно только суперадмину.
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{id}",
+    tag = "api-keys",
+    params(("id" = i64, Path, description = "Id ключа")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Ключ отозван, в ответе его id"),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse),
+        (status = 404, description = "Действующий ключ не найден", body = ErrorResponse)
+    )
+)]
+fn revoke_api_key() {}
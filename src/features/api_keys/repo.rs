@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::dao::ApiKey;
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "ApiKey repo: create", skip_all)]
+    pub async fn create(
+        &self,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        permissions: Vec<String>,
+        created_by: i64,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, sqlx::Error> {
+        tracing::debug!("ApiKey repo: Adding API key {}", name);
+        let _timer = telemetry::query_timer("api_keys", "create");
+        sqlx::query_as::<_, ApiKey>(
+            "INSERT INTO api_key (name, prefix, key_hash, permissions, created_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, name, prefix, permissions, created_by, created_at, expires_at,
+                last_used_at, revoked_at",
+        )
+        .bind(name)
+        .bind(prefix)
+        .bind(key_hash)
+        .bind(permissions)
+        .bind(created_by)
+        .bind(expires_at)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "ApiKey repo: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<ApiKey>, sqlx::Error> {
+        tracing::debug!("ApiKey repo: Getting vector API keys");
+        let _timer = telemetry::query_timer("api_keys", "get_all");
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT id, name, prefix, permissions, created_by, created_at, expires_at,
+                last_used_at, revoked_at
+            FROM api_key
+            ORDER BY id",
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Возвращает `false`, если ключа нет или он уже отозван.
+    #[tracing::instrument(name = "ApiKey repo: revoke", skip_all)]
+    pub async fn revoke(&self, id: i64) -> Result<bool, sqlx::Error> {
+        tracing::debug!("ApiKey repo: Revoking API key {}", id);
+        let _timer = telemetry::query_timer("api_keys", "revoke");
+        sqlx::query(
+            "UPDATE api_key SET revoked_at = CURRENT_TIMESTAMP WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
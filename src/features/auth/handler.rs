@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, http::StatusCode};
+use serde_json::json;
+use tracing::Instrument;
+
+use super::AuthEmployee;
+use super::logic::{Logic, LoginOutcome};
+use crate::models::dto::{
+    Error, ErrorResponse, LocalePreference, Login, MfaLogin, PasswordChange, PasswordForgot,
+    PasswordReset, RecoveryCodes, Refresh, Session, Token, TotpCode, TotpSetup,
+};
+use crate::rate_limit;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn login(
+        State(handler): State<Arc<Handler>>,
+        headers: HeaderMap,
+        Json(payload): Json<Login>,
+    ) -> Response {
+        // Пароль в span не попадает
+        let span = tracing::info_span!("Auth handler: login", email = ?payload.email);
+        let user_agent = user_agent(&headers);
+        async {
+            match handler.logic.login(payload, user_agent).await {
+                Ok(LoginOutcome::Token(token)) => {
+                    (StatusCode::OK, Json(json!(token))).into_response()
+                }
+                Ok(LoginOutcome::MfaRequired(challenge)) => {
+                    (StatusCode::ACCEPTED, Json(json!(challenge))).into_response()
+                }
+                Err(Error::TooManyRequests(_, retry_after)) => {
+                    rate_limit::too_many_requests(retry_after)
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to log in: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response))).into_response()
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Второй шаг входа с подключённой 2FA.
+    pub async fn login_mfa(
+        State(handler): State<Arc<Handler>>,
+        headers: HeaderMap,
+        Json(payload): Json<MfaLogin>,
+    ) -> Response {
+        let span = tracing::info_span!("Auth handler: login_mfa");
+        let user_agent = user_agent(&headers);
+        async {
+            match handler.logic.login_mfa(payload, user_agent).await {
+                Ok(token) => (StatusCode::OK, Json(json!(token))).into_response(),
+                Err(Error::TooManyRequests(_, retry_after)) => {
+                    rate_limit::too_many_requests(retry_after)
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to log in with second factor: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response))).into_response()
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn refresh(
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<Refresh>,
+    ) -> Result<(StatusCode, Json<Token>), (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: refresh");
+        async {
+            match handler.logic.refresh(payload).await {
+                Ok(token) => Ok((StatusCode::OK, Json(token))),
+                Err(err) => {
+                    tracing::warn!("Failed to refresh tokens: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Завершение текущей сессии.
+    pub async fn logout(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: logout", id = auth.id);
+        async {
+            match handler.logic.logout(auth, false).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::error!("Failed to log out: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Завершение всех сессий сотрудника, включая текущую.
+    pub async fn logout_everywhere(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: logout_everywhere", id = auth.id);
+        async {
+            match handler.logic.logout(auth, true).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::error!("Failed to log out everywhere: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_sessions(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+    ) -> Result<(StatusCode, Json<Vec<Session>>), (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: get_sessions", id = auth.id);
+        async {
+            match handler.logic.get_sessions(auth).await {
+                Ok(sessions) => Ok((StatusCode::OK, Json(sessions))),
+                Err(err) => {
+                    tracing::error!("Failed to get sessions: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn change_password(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<PasswordChange>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: change_password", id = auth.id);
+        async {
+            match handler.logic.change_password(auth, payload).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::warn!("Failed to change password: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn forgot_password(
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<PasswordForgot>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: forgot_password", email = ?payload.email);
+        async {
+            match handler.logic.forgot_password(payload).await {
+                Ok(()) => Ok(StatusCode::ACCEPTED),
+                Err(err) => {
+                    tracing::error!("Failed to request password reset: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn reset_password(
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<PasswordReset>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: reset_password");
+        async {
+            match handler.logic.reset_password(payload).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::warn!("Failed to reset password: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Выбор языка сообщений для своей учётной записи.
+    pub async fn set_locale(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<LocalePreference>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: set_locale", id = auth.id);
+        async {
+            match handler.logic.set_locale(auth, payload).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::error!("Failed to set locale: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn setup_totp(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+    ) -> Result<(StatusCode, Json<TotpSetup>), (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: setup_totp", id = auth.id);
+        async {
+            match handler.logic.setup_totp(auth).await {
+                Ok(setup) => Ok((StatusCode::OK, Json(setup))),
+                Err(err) => {
+                    tracing::warn!("Failed to set up two-factor authentication: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn enable_totp(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<TotpCode>,
+    ) -> Result<(StatusCode, Json<RecoveryCodes>), (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: enable_totp", id = auth.id);
+        async {
+            match handler.logic.enable_totp(auth, payload).await {
+                Ok(codes) => Ok((StatusCode::OK, Json(codes))),
+                Err(err) => {
+                    tracing::warn!("Failed to enable two-factor authentication: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn disable_totp(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<TotpCode>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Auth handler: disable_totp", id = auth.id);
+        async {
+            match handler.logic.disable_totp(auth, payload).await {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(err) => {
+                    tracing::warn!("Failed to disable two-factor authentication: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
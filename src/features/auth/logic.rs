@@ -0,0 +1,624 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use tracing::Instrument;
+
+use crate::i18n;
+use crate::mail::Mail;
+use crate::models::dao::{self, Role};
+use crate::models::dto;
+use crate::password;
+
+use super::repo::Rotation;
+use super::token::{ACCESS_TOKEN_TTL, MFA_TOKEN_TTL, digest};
+use super::{AuthEmployee, Settings, totp};
+
+/// Результат проверки пароля: токены сессии или запрос второго фактора.
+pub enum LoginOutcome {
+    Token(dto::Token),
+    MfaRequired(dto::MfaChallenge),
+}
+
+pub struct Logic {
+    repo: Arc<super::Repo>,
+    settings: Settings,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<super::Repo>, settings: Settings) -> Self {
+        Logic { repo, settings }
+    }
+
+    /// Проверяет email и пароль, открывает сессию и выдаёт её токены. Если у сотрудника
+    /// подключена 2FA, вместо токенов выдаётся `mfa_token` для `login_mfa`.
+    ///
+    /// Попытки ограничиваются по email, а после `lockout_threshold` неудачных подряд
+    /// учётная запись блокируется до разблокировки суперадмином.
+    #[tracing::instrument(name = "Auth logic: login", skip_all)]
+    pub async fn login(
+        &self,
+        payload: dto::Login,
+        user_agent: Option<String>,
+    ) -> Result<LoginOutcome, dto::Error> {
+        tracing::debug!("Auth logic: Logging in");
+        let (Some(email), Some(password)) = (payload.email, payload.password) else {
+            return Err(dto::Error::BadRequest(
+                "Fields 'email' and 'password' are required.".to_string(),
+            ));
+        };
+
+        self.settings
+            .limiter
+            .check(&format!("account:{}", email.to_lowercase()))
+            .await
+            .map_err(|retry_after| {
+                tracing::warn!("Too many login attempts for {email}");
+                dto::Error::TooManyRequests("Too many login attempts".to_string(), retry_after)
+            })?;
+
+        let credentials =
+            self.repo.find_credentials(&email).await.map_err(|_| {
+                dto::Error::InternalServerError("Internal database error".to_string())
+            })?;
+        // Неизвестный email проверяется так же долго, как известный
+        let Some(credentials) = credentials else {
+            password::verify_dummy(&password).await?;
+            return Err(invalid_credentials());
+        };
+
+        if !password::verify(&password, &credentials.password).await? {
+            self.register_failed_login(credentials.id).await?;
+            return Err(invalid_credentials());
+        }
+
+        // Блокировка видна только тому, кто знает пароль
+        if credentials.locked_at.is_some() {
+            return Err(dto::Error::Locked(
+                "Account is locked, contact an administrator".to_string(),
+            ));
+        }
+
+        if !credentials.active {
+            return Err(dto::Error::Forbidden("Employee is deactivated".to_string()));
+        }
+
+        if password::needs_rehash(&credentials.password) {
+            self.rehash(credentials.id, &password).await;
+        }
+        // Счётчик неудач сбрасывается только после второго фактора, чтобы подбор кода
+        // тоже приводил к блокировке
+        if credentials.totp_enabled {
+            tracing::info!(
+                "Employee {} passed password check, 2FA required",
+                credentials.id
+            );
+            return Ok(LoginOutcome::MfaRequired(dto::MfaChallenge {
+                mfa_token: self.settings.keys.mfa_token(credentials.id)?,
+                expires_in: MFA_TOKEN_TTL.as_secs(),
+            }));
+        }
+
+        self.open_session(&credentials, user_agent)
+            .await
+            .map(LoginOutcome::Token)
+    }
+
+    /// Второй шаг входа: код из приложения или код восстановления для `mfa_token`.
+    ///
+    /// Неверный код считается неудачной попыткой входа наравне с неверным паролем.
+    #[tracing::instrument(name = "Auth logic: login_mfa", skip_all)]
+    pub async fn login_mfa(
+        &self,
+        payload: dto::MfaLogin,
+        user_agent: Option<String>,
+    ) -> Result<dto::Token, dto::Error> {
+        tracing::debug!("Auth logic: Logging in with second factor");
+        let Some(mfa_token) = payload.mfa_token else {
+            return Err(dto::Error::BadRequest(
+                "Field 'mfa_token' is required.".to_string(),
+            ));
+        };
+        let id = self.settings.keys.verify_mfa(&mfa_token)?;
+
+        self.settings
+            .limiter
+            .check(&format!("mfa:{id}"))
+            .await
+            .map_err(|retry_after| {
+                tracing::warn!("Too many two-factor attempts for employee {id}");
+                dto::Error::TooManyRequests("Too many login attempts".to_string(), retry_after)
+            })?;
+
+        let credentials = self
+            .repo
+            .find_credentials_by_id(id)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(invalid_credentials)?;
+        if credentials.locked_at.is_some() {
+            return Err(dto::Error::Locked(
+                "Account is locked, contact an administrator".to_string(),
+            ));
+        }
+        if !credentials.active {
+            return Err(dto::Error::Forbidden("Employee is deactivated".to_string()));
+        }
+
+        let code = dto::TotpCode {
+            code: payload.code,
+            recovery_code: payload.recovery_code,
+        };
+        if !self.verify_second_factor(id, code).await? {
+            self.register_failed_login(id).await?;
+            return Err(dto::Error::Unauthorized(
+                "Invalid two-factor code".to_string(),
+            ));
+        }
+        self.open_session(&credentials, user_agent).await
+    }
+
+    /// Обменивает refresh токен на новую пару токенов той же сессии.
+    #[tracing::instrument(name = "Auth logic: refresh", skip_all)]
+    pub async fn refresh(&self, payload: dto::Refresh) -> Result<dto::Token, dto::Error> {
+        tracing::debug!("Auth logic: Refreshing tokens");
+        let Some(token) = payload.refresh_token else {
+            return Err(dto::Error::BadRequest(
+                "Field 'refresh_token' is required.".to_string(),
+            ));
+        };
+        let claims = self.settings.keys.verify_refresh(&token)?;
+        let id: i64 = claims.sub.parse().map_err(|_| {
+            dto::Error::Unauthorized("Invalid or expired refresh token".to_string())
+        })?;
+
+        let (refresh_token, jti) =
+            self.settings
+                .keys
+                .refresh_token(id, claims.sid, self.settings.refresh_ttl)?;
+        let rotation = self
+            .repo
+            .rotate_refresh_token(claims.sid, &claims.jti, &jti, self.session_expires_at())
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        match rotation {
+            Rotation::Rotated(role) => self.token(id, role, claims.sid, refresh_token),
+            Rotation::Reused => {
+                tracing::warn!(
+                    "Refresh token reuse detected, session {} of employee {id} revoked",
+                    claims.sid
+                );
+                Err(dto::Error::Unauthorized(
+                    "Invalid or expired refresh token".to_string(),
+                ))
+            }
+            Rotation::Invalid => Err(dto::Error::Unauthorized(
+                "Invalid or expired refresh token".to_string(),
+            )),
+        }
+    }
+
+    #[tracing::instrument(name = "Auth logic: get_sessions", skip_all)]
+    pub async fn get_sessions(&self, auth: AuthEmployee) -> Result<Vec<dto::Session>, dto::Error> {
+        tracing::debug!("Auth logic: Getting sessions of employee {}", auth.id);
+        self.repo
+            .get_sessions(auth.id)
+            .await
+            .map(|sessions| {
+                sessions
+                    .into_iter()
+                    .map(|session| session.to_dto(auth.session))
+                    .collect()
+            })
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    /// Завершает текущую сессию или, если `everywhere`, все сессии сотрудника.
+    #[tracing::instrument(name = "Auth logic: logout", skip_all)]
+    pub async fn logout(&self, auth: AuthEmployee, everywhere: bool) -> Result<(), dto::Error> {
+        tracing::debug!("Auth logic: Logging out employee {}", auth.id);
+        let only = (!everywhere).then_some(auth.session);
+        let revoked = self
+            .repo
+            .revoke_sessions(auth.id, only, None)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!(
+            "Employee {} logged out, {revoked} sessions revoked",
+            auth.id
+        );
+        Ok(())
+    }
+
+    fn token(
+        &self,
+        id: i64,
+        role: Role,
+        session: i64,
+        refresh_token: String,
+    ) -> Result<dto::Token, dto::Error> {
+        Ok(dto::Token {
+            access_token: self.settings.keys.access_token(id, role, session)?,
+            token_type: "Bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL.as_secs(),
+            refresh_token,
+        })
+    }
+
+    /// Сбрасывает счётчик неудачных входов и открывает сессию с новыми токенами.
+    async fn open_session(
+        &self,
+        credentials: &dao::Credentials,
+        user_agent: Option<String>,
+    ) -> Result<dto::Token, dto::Error> {
+        self.repo
+            .reset_failed_logins(credentials.id)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        let session = self
+            .repo
+            .create_session(
+                credentials.id,
+                user_agent.as_deref(),
+                self.session_expires_at(),
+            )
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        let (refresh_token, jti) =
+            self.settings
+                .keys
+                .refresh_token(credentials.id, session, self.settings.refresh_ttl)?;
+        self.repo
+            .add_refresh_token(session, &jti)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!("Employee {} logged in, session {session}", credentials.id);
+        self.token(credentials.id, credentials.role, session, refresh_token)
+    }
+
+    async fn register_failed_login(&self, id: i64) -> Result<(), dto::Error> {
+        if self.settings.lockout_threshold == 0 {
+            return Ok(());
+        }
+        let locked = self
+            .repo
+            .register_failed_login(id, self.settings.lockout_threshold)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        if locked {
+            tracing::warn!(
+                "Employee {id} locked after {} failed logins",
+                self.settings.lockout_threshold
+            );
+        }
+        Ok(())
+    }
+
+    fn session_expires_at(&self) -> DateTime<Utc> {
+        Utc::now()
+            + chrono::Duration::from_std(self.settings.refresh_ttl).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Меняет пароль сотрудника после проверки текущего и завершает остальные его сессии.
+    #[tracing::instrument(name = "Auth logic: change_password", skip_all)]
+    pub async fn change_password(
+        &self,
+        auth: AuthEmployee,
+        payload: dto::PasswordChange,
+    ) -> Result<(), dto::Error> {
+        let id = auth.id;
+        tracing::debug!("Auth logic: Changing password of employee {id}");
+        let (Some(current), Some(new)) = (payload.current_password, payload.new_password) else {
+            return Err(dto::Error::BadRequest(
+                "Fields 'current_password' and 'new_password' are required.".to_string(),
+            ));
+        };
+
+        let hash = self
+            .repo
+            .find_password(id)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(|| dto::Error::NotFound("Employee not found".to_string()))?;
+        if !password::verify(&current, &hash).await? {
+            return Err(dto::Error::BadRequest(
+                "Current password is incorrect.".to_string(),
+            ));
+        }
+        password::check_policy(&new)?;
+
+        let hash = password::hash(&new).await?;
+        self.repo
+            .update_password(id, &hash)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        self.repo
+            .revoke_sessions(id, None, Some(auth.session))
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!("Employee {id} changed password");
+        Ok(())
+    }
+
+    /// Отправляет письмо со ссылкой восстановления, если сотрудник с таким email есть и активен.
+    ///
+    /// Ответ одинаков для любого email, чтобы по нему нельзя было перебирать адреса.
+    /// Поиск сотрудника, токен и письмо поэтому уходят в фон: иначе известный адрес
+    /// отвечал бы дольше на запись токена. Ошибки только пишутся в лог.
+    #[tracing::instrument(name = "Auth logic: forgot_password", skip_all)]
+    pub async fn forgot_password(&self, payload: dto::PasswordForgot) -> Result<(), dto::Error> {
+        tracing::debug!("Auth logic: Requesting password reset");
+        let Some(email) = payload.email else {
+            return Err(dto::Error::BadRequest(
+                "Field 'email' is required.".to_string(),
+            ));
+        };
+
+        let repo = self.repo.clone();
+        let mailer = self.settings.mailer.clone();
+        let (reset_ttl, reset_url) = (self.settings.reset_ttl, self.settings.reset_url.clone());
+        // Язык запроса в фоновой задаче уже недоступен
+        let request_locale = i18n::current();
+        let task = async move {
+            let credentials = match repo.find_credentials(&email).await {
+                Ok(credentials) => credentials,
+                Err(err) => {
+                    tracing::error!("Failed to request password reset: {err}");
+                    return;
+                }
+            };
+            let Some(credentials) = credentials.filter(|c| c.active) else {
+                tracing::info!("Password reset requested for unknown or inactive {email}");
+                return;
+            };
+
+            let token = hex::encode(rand::rng().random::<[u8; 32]>());
+            let expires_at =
+                Utc::now() + chrono::Duration::from_std(reset_ttl).unwrap_or(chrono::Duration::MAX);
+            if let Err(err) = repo
+                .create_password_reset(credentials.id, &digest(&token), expires_at)
+                .await
+            {
+                tracing::error!("Failed to request password reset: {err}");
+                return;
+            }
+
+            let link = match &reset_url {
+                Some(url) => url.replace("{token}", &token),
+                None => format!("Token: {token}"),
+            };
+            // Письмо на языке сотрудника, а если он не выбран - на языке запроса
+            let locale = credentials
+                .locale
+                .and_then(|locale| locale.parse().ok())
+                .unwrap_or(request_locale);
+            let mail = Mail {
+                to: email,
+                subject: locale.translate("Password reset"),
+                body: locale.format(
+                    "A password reset was requested for your account.\n\n{}\n\n\
+                    The link is valid for {} minutes. If you didn't request it, ignore this email.",
+                    &[&link, &(reset_ttl.as_secs() / 60)],
+                ),
+            };
+            let id = credentials.id;
+            match mailer.send(mail).await {
+                Ok(()) => tracing::info!("Password reset mail sent to employee {id}"),
+                Err(err) => tracing::error!("Failed to send password reset mail: {err}"),
+            }
+        };
+        tokio::spawn(task.instrument(tracing::Span::current()));
+        Ok(())
+    }
+
+    /// Устанавливает новый пароль по токену из письма и снимает блокировку после
+    /// неудачных входов. Токен одноразовый.
+    #[tracing::instrument(name = "Auth logic: reset_password", skip_all)]
+    pub async fn reset_password(&self, payload: dto::PasswordReset) -> Result<(), dto::Error> {
+        tracing::debug!("Auth logic: Resetting password by token");
+        let (Some(token), Some(new)) = (payload.token, payload.new_password) else {
+            return Err(dto::Error::BadRequest(
+                "Fields 'token' and 'new_password' are required.".to_string(),
+            ));
+        };
+        password::check_policy(&new)?;
+
+        let hash = password::hash(&new).await?;
+        let id = self
+            .repo
+            .reset_password(&digest(&token), &hash)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(|| dto::Error::BadRequest("Invalid or expired token.".to_string()))?;
+        tracing::info!("Employee {id} reset password");
+        Ok(())
+    }
+
+    /// Сохраняет язык сообщений сотрудника, `None` - выбирать по `Accept-Language`.
+    #[tracing::instrument(name = "Auth logic: set_locale", skip_all)]
+    pub async fn set_locale(
+        &self,
+        auth: AuthEmployee,
+        payload: dto::LocalePreference,
+    ) -> Result<(), dto::Error> {
+        tracing::debug!("Auth logic: Setting locale of employee {}", auth.id);
+        let locale = payload.locale.map(|locale| locale.code());
+        self.repo
+            .set_locale(auth.id, locale)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    /// Создаёт новый секрет TOTP. Подключается 2FA только после `enable_totp`
+    /// с кодом из приложения, до этого секрет можно запросить заново.
+    #[tracing::instrument(name = "Auth logic: setup_totp", skip_all)]
+    pub async fn setup_totp(&self, auth: AuthEmployee) -> Result<dto::TotpSetup, dto::Error> {
+        let id = auth.id;
+        tracing::debug!("Auth logic: Setting up TOTP of employee {id}");
+        let state = self.find_totp(id).await?;
+        if state.enabled {
+            return Err(two_factor_enabled());
+        }
+
+        let secret = totp::generate_secret();
+        let provisioning_uri =
+            totp::provisioning_uri(&secret, &self.settings.totp_issuer, &state.email)?;
+        let stored =
+            self.repo.set_totp_secret(id, &secret).await.map_err(|_| {
+                dto::Error::InternalServerError("Internal database error".to_string())
+            })?;
+        if !stored {
+            return Err(two_factor_enabled());
+        }
+        Ok(dto::TotpSetup {
+            secret,
+            provisioning_uri,
+        })
+    }
+
+    /// Подключает 2FA, если код из приложения подходит к секрету из `setup_totp`.
+    /// Возвращает коды восстановления, в базе хранятся только их хеши.
+    #[tracing::instrument(name = "Auth logic: enable_totp", skip_all)]
+    pub async fn enable_totp(
+        &self,
+        auth: AuthEmployee,
+        payload: dto::TotpCode,
+    ) -> Result<dto::RecoveryCodes, dto::Error> {
+        let id = auth.id;
+        tracing::debug!("Auth logic: Enabling TOTP of employee {id}");
+        let Some(code) = payload.code else {
+            return Err(dto::Error::BadRequest(
+                "Field 'code' is required.".to_string(),
+            ));
+        };
+        let state = self.find_totp(id).await?;
+        if state.enabled {
+            return Err(two_factor_enabled());
+        }
+        let Some(secret) = state.secret else {
+            return Err(dto::Error::BadRequest(
+                "Two-factor setup is not started.".to_string(),
+            ));
+        };
+        if !self.use_totp_code(id, &secret, &code).await? {
+            return Err(dto::Error::BadRequest(
+                "Invalid two-factor code.".to_string(),
+            ));
+        }
+
+        let recovery_codes = totp::generate_recovery_codes();
+        let hashes: Vec<String> = recovery_codes
+            .iter()
+            .map(|code| digest(&totp::normalize_recovery_code(code)))
+            .collect();
+        self.repo
+            .enable_totp(id, &hashes)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!("Employee {id} enabled two-factor authentication");
+        Ok(dto::RecoveryCodes { recovery_codes })
+    }
+
+    /// Отключает 2FA по коду из приложения или коду восстановления. Если 2FA
+    /// обязательна, менеджеры и суперадмины отключить её не могут.
+    #[tracing::instrument(name = "Auth logic: disable_totp", skip_all)]
+    pub async fn disable_totp(
+        &self,
+        auth: AuthEmployee,
+        payload: dto::TotpCode,
+    ) -> Result<(), dto::Error> {
+        let id = auth.id;
+        tracing::debug!("Auth logic: Disabling TOTP of employee {id}");
+        if self.settings.require_2fa && auth.role.is_privileged() {
+            return Err(dto::Error::Forbidden(
+                "Two-factor authentication is required for this role".to_string(),
+            ));
+        }
+        if !self.find_totp(id).await?.enabled {
+            return Err(dto::Error::Conflict(
+                "Two-factor authentication is not enabled".to_string(),
+            ));
+        }
+        if !self.verify_second_factor(id, payload).await? {
+            return Err(dto::Error::BadRequest(
+                "Invalid two-factor code.".to_string(),
+            ));
+        }
+        self.repo
+            .disable_totp(id)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?;
+        tracing::info!("Employee {id} disabled two-factor authentication");
+        Ok(())
+    }
+
+    async fn find_totp(&self, id: i64) -> Result<dao::Totp, dto::Error> {
+        self.repo
+            .find_totp(id)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(|| dto::Error::NotFound("Employee not found".to_string()))
+    }
+
+    /// Проверяет код из приложения или гасит код восстановления подключённой 2FA.
+    async fn verify_second_factor(
+        &self,
+        id: i64,
+        payload: dto::TotpCode,
+    ) -> Result<bool, dto::Error> {
+        match (payload.code, payload.recovery_code) {
+            (Some(code), _) => {
+                let Some(secret) = self.find_totp(id).await?.secret else {
+                    return Ok(false);
+                };
+                self.use_totp_code(id, &secret, &code).await
+            }
+            (None, Some(recovery_code)) => {
+                let hash = digest(&totp::normalize_recovery_code(&recovery_code));
+                let used = self.repo.use_recovery_code(id, &hash).await.map_err(|_| {
+                    dto::Error::InternalServerError("Internal database error".to_string())
+                })?;
+                if used {
+                    tracing::info!("Employee {id} used a recovery code");
+                }
+                Ok(used)
+            }
+            (None, None) => Err(dto::Error::BadRequest(
+                "Field 'code' or 'recovery_code' is required.".to_string(),
+            )),
+        }
+    }
+
+    /// Код подходит, если верен и его шаг позже последнего принятого: перехваченный
+    /// код нельзя предъявить повторно.
+    async fn use_totp_code(&self, id: i64, secret: &str, code: &str) -> Result<bool, dto::Error> {
+        let Some(step) = totp::matching_step(secret, code)? else {
+            return Ok(false);
+        };
+        self.repo
+            .use_totp_step(id, step)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    /// Пересчитывает устаревший хеш, пока известен открытый пароль. Ошибка не мешает входу:
+    /// попытка повторится при следующем.
+    async fn rehash(&self, id: i64, password: &str) {
+        match password::hash(password).await {
+            Ok(hash) => match self.repo.update_password(id, &hash).await {
+                Ok(()) => tracing::info!("Password hash of employee {id} upgraded"),
+                Err(_) => tracing::warn!("Failed to store upgraded password hash of employee {id}"),
+            },
+            Err(err) => tracing::warn!("Failed to upgrade password hash of employee {id}: {err:?}"),
+        }
+    }
+}
+
+fn two_factor_enabled() -> dto::Error {
+    dto::Error::Conflict("Two-factor authentication is already enabled".to_string())
+}
+
+/// Одинаковый ответ для неизвестного email и неверного пароля.
+fn invalid_credentials() -> dto::Error {
+    dto::Error::Unauthorized("Invalid email or password".to_string())
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::routing::{get, post, put};
+
+use crate::features::auth::{handler::Handler, logic::Logic, repo::Repo};
+use crate::mail::{LogMailer, Mailer};
+use crate::rate_limit::RateLimiter;
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+pub mod token;
+pub mod totp;
+
+pub use token::{AuthEmployee, Authenticator, Keys, Principal};
+
+/// Настройки входа и восстановления пароля.
+///
+/// ## Пример
+/// ```ignore
+/// let settings = Settings::new(keys)
+///     .with_limiter(Arc::new(RateLimiter::in_memory(5)))
+///     .with_lockout_threshold(5)
+///     .with_mailer(mailer);
+/// ```
+pub struct Settings {
+    pub keys: Arc<Keys>,
+    /// Ограничение попыток входа по email.
+    pub limiter: Arc<RateLimiter>,
+    /// Неудачных входов до блокировки учётной записи, 0 - не блокировать.
+    pub lockout_threshold: u32,
+    pub mailer: Arc<dyn Mailer>,
+    pub reset_ttl: Duration,
+    /// Сколько сессия живёт без обновления токенов.
+    pub refresh_ttl: Duration,
+    /// Ссылка в письме восстановления, `{token}` заменяется токеном.
+    pub reset_url: Option<String>,
+    /// Название сервиса в приложении-аутентификаторе.
+    pub totp_issuer: String,
+    /// Запрет отключать 2FA менеджерам и суперадминам.
+    pub require_2fa: bool,
+}
+
+impl Settings {
+    pub fn new(keys: Arc<Keys>) -> Self {
+        Settings {
+            keys,
+            limiter: Arc::new(RateLimiter::in_memory(0)),
+            lockout_threshold: 5,
+            mailer: Arc::new(LogMailer),
+            reset_ttl: Duration::from_secs(3600),
+            refresh_ttl: Duration::from_secs(30 * 24 * 3600),
+            reset_url: None,
+            totp_issuer: "MDS".to_string(),
+            require_2fa: false,
+        }
+    }
+
+    pub fn with_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    pub fn with_lockout_threshold(mut self, lockout_threshold: u32) -> Self {
+        self.lockout_threshold = lockout_threshold;
+        self
+    }
+
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    pub fn with_refresh_ttl(mut self, refresh_ttl: Duration) -> Self {
+        self.refresh_ttl = refresh_ttl;
+        self
+    }
+
+    pub fn with_password_reset(mut self, ttl: Duration, url: Option<String>) -> Self {
+        self.reset_ttl = ttl;
+        self.reset_url = url;
+        self
+    }
+
+    pub fn with_two_factor(mut self, issuer: &str, required: bool) -> Self {
+        self.totp_issuer = issuer.to_string();
+        self.require_2fa = required;
+        self
+    }
+}
+
+pub fn new(pool: &sqlx::PgPool, settings: Settings) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo, settings));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/auth/login", post(Handler::login))
+        .route("/auth/login/2fa", post(Handler::login_mfa))
+        .route("/auth/refresh", post(Handler::refresh))
+        .route("/auth/logout", post(Handler::logout))
+        .route(
+            "/auth/sessions",
+            get(Handler::get_sessions).delete(Handler::logout_everywhere),
+        )
+        .route("/auth/password", post(Handler::change_password))
+        .route("/auth/password/forgot", post(Handler::forgot_password))
+        .route("/auth/password/reset", post(Handler::reset_password))
+        .route("/auth/locale", put(Handler::set_locale))
+        .route("/auth/2fa/setup", post(Handler::setup_totp))
+        .route("/auth/2fa/enable", post(Handler::enable_totp))
+        .route("/auth/2fa/disable", post(Handler::disable_totp))
+        .with_state(handler)
+}
@@ -0,0 +1,287 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::i18n::Locale;
+use crate::models::dto::{
+    ErrorResponse, LocalePreference, Login, MfaChallenge, MfaLogin, PasswordChange, PasswordForgot,
+    PasswordReset, RecoveryCodes, Refresh, Session, Token, TotpCode, TotpSetup,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        login,
+        login_mfa,
+        refresh,
+        logout,
+        get_sessions,
+        logout_everywhere,
+        change_password,
+        forgot_password,
+        reset_password,
+        set_locale,
+        setup_totp,
+        enable_totp,
+        disable_totp
+    ),
+    components(schemas(
+        Login,
+        Token,
+        Refresh,
+        Session,
+        PasswordChange,
+        PasswordForgot,
+        PasswordReset,
+        MfaChallenge,
+        MfaLogin,
+        TotpCode,
+        TotpSetup,
+        RecoveryCodes,
+        LocalePreference,
+        Locale,
+        ErrorResponse
+    )),
+    modifiers(&BearerAuth),
+    tags((name = "auth", description = "Вход сотрудников, сессии, смена пароля и 2FA"))
+)]
+pub struct ApiDoc;
+
+/// Схема `bearer` для маршрутов, требующих access токен, и `api_key` для маршрутов,
+/// открытых интеграциям (`features::auth::Principal`).
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+        );
+    }
+}
+
+/// Вход по email и паролю
+///
+/// Открывает сессию и выдаёт access и refresh токены, а при подключённой 2FA - `mfa_token`
+/// для `/auth/login/2fa`. Попытки ограничены по IP и по email. После нескольких неудачных попыток подряд
+/// учётная запись блокируется до разблокировки суперадмином.
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = Login,
+    responses(
+        (status = 200, description = "Токены новой сессии", body = Token),
+        (status = 202, description = "Пароль верен, нужен код 2FA", body = MfaChallenge),
+        (status = 400, description = "Не заполнены email или пароль", body = ErrorResponse),
+        (status = 401, description = "Неверный email или пароль", body = ErrorResponse),
+        (status = 403, description = "Сотрудник деактивирован", body = ErrorResponse),
+        (status = 423, description = "Учётная запись заблокирована, пароль верен", body = ErrorResponse),
+        (status = 429, description = "Слишком много попыток, см. `Retry-After`", body = ErrorResponse)
+    )
+)]
+fn login() {}
+
+/// Вход, второй шаг
+///
+/// Код из приложения-аутентификатора или один из кодов восстановления. Каждый код
+/// принимается один раз, неверный код считается неудачной попыткой входа.
+#[utoipa::path(
+    post,
+    path = "/auth/login/2fa",
+    tag = "auth",
+    request_body = MfaLogin,
+    responses(
+        (status = 200, description = "Токены новой сессии", body = Token),
+        (status = 400, description = "Не заполнены mfa_token или код", body = ErrorResponse),
+        (status = 401, description = "mfa_token недействителен или неверный код", body = ErrorResponse),
+        (status = 403, description = "Сотрудник деактивирован", body = ErrorResponse),
+        (status = 423, description = "Учётная запись заблокирована", body = ErrorResponse),
+        (status = 429, description = "Слишком много попыток, см. `Retry-After`", body = ErrorResponse)
+    )
+)]
+fn login_mfa() {}
+
+/// Обновление токенов
+///
+/// Refresh токен одноразовый: в ответ выдаётся новая пара. Повторное предъявление
+/// использованного токена отзывает всю сессию.
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    tag = "auth",
+    request_body = Refresh,
+    responses(
+        (status = 200, description = "Новые токены той же сессии", body = Token),
+        (status = 400, description = "Не заполнен refresh_token", body = ErrorResponse),
+        (status = 401, description = "Токен недействителен, использован, сессия отозвана или сотрудник деактивирован", body = ErrorResponse),
+        (status = 429, description = "Слишком много запросов, см. `Retry-After`", body = ErrorResponse)
+    )
+)]
+fn refresh() {}
+
+/// Выход из текущей сессии
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Сессия завершена"),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse)
+    )
+)]
+fn logout() {}
+
+/// Активные сессии
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Сессии сотрудника, последние использованные первыми", body = [Session]),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse)
+    )
+)]
+fn get_sessions() {}
+
+/// Выход из всех сессий
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Все сессии сотрудника завершены, включая текущую"),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse)
+    )
+)]
+fn logout_everywhere() {}
+
+/// Смена своего пароля
+///
+/// Новый пароль должен соответствовать политике паролей. Остальные сессии сотрудника завершаются.
+#[utoipa::path(
+    post,
+    path = "/auth/password",
+    tag = "auth",
+    request_body = PasswordChange,
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Пароль изменён"),
+        (status = 400, description = "Неверный текущий пароль или новый не соответствует политике", body = ErrorResponse),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse)
+    )
+)]
+fn change_password() {}
+
+/// Запрос восстановления пароля
+///
+/// Если сотрудник с таким email есть и активен, ему уходит письмо с одноразовым токеном.
+/// Ответ не зависит от того, найден ли email.
+#[utoipa::path(
+    post,
+    path = "/auth/password/forgot",
+    tag = "auth",
+    request_body = PasswordForgot,
+    responses(
+        (status = 202, description = "Запрос принят"),
+        (status = 400, description = "Не заполнен email", body = ErrorResponse),
+        (status = 429, description = "Слишком много запросов, см. `Retry-After`", body = ErrorResponse)
+    )
+)]
+fn forgot_password() {}
+
+/// Новый пароль по токену из письма
+#[utoipa::path(
+    post,
+    path = "/auth/password/reset",
+    tag = "auth",
+    request_body = PasswordReset,
+    responses(
+        (status = 204, description = "Пароль изменён"),
+        (status = 400, description = "Токен недействителен, истёк или пароль не соответствует политике", body = ErrorResponse),
+        (status = 429, description = "Слишком много запросов, см. `Retry-After`", body = ErrorResponse)
+    )
+)]
+fn reset_password() {}
+
+/// Язык сообщений
+///
+/// Сообщения об ошибках и письма для сотрудника будут на выбранном языке независимо
+/// от `Accept-Language`. `null` возвращает выбор по заголовку.
+#[utoipa::path(
+    put,
+    path = "/auth/locale",
+    tag = "auth",
+    request_body = LocalePreference,
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Язык сохранён"),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 422, description = "Неподдерживаемый язык")
+    )
+)]
+fn set_locale() {}
+
+/// Начало подключения 2FA
+///
+/// Выдаёт новый секрет и ссылку для QR-кода. 2FA начинает действовать после `/auth/2fa/enable`.
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/setup",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Секрет и ссылка `otpauth://`", body = TotpSetup),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 409, description = "2FA уже подключена", body = ErrorResponse)
+    )
+)]
+fn setup_totp() {}
+
+/// Подключение 2FA
+///
+/// Проверяет код из приложения и возвращает коды восстановления. Они показываются один раз.
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/enable",
+    tag = "auth",
+    request_body = TotpCode,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "2FA подключена", body = RecoveryCodes),
+        (status = 400, description = "Неверный код или подключение не начато", body = ErrorResponse),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 409, description = "2FA уже подключена", body = ErrorResponse)
+    )
+)]
+fn enable_totp() {}
+
+/// Отключение 2FA
+///
+/// Нужен код из приложения или код восстановления. Если 2FA обязательна, менеджеры
+/// и суперадмины отключить её не могут.
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/disable",
+    tag = "auth",
+    request_body = TotpCode,
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "2FA отключена"),
+        (status = 400, description = "Неверный код", body = ErrorResponse),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "2FA обязательна для роли", body = ErrorResponse),
+        (status = 409, description = "2FA не подключена", body = ErrorResponse)
+    )
+)]
+fn disable_totp() {}
@@ -0,0 +1,618 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::dao::{self, Role};
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "Auth repo: find_credentials", skip_all)]
+    pub async fn find_credentials(
+        &self,
+        email: &str,
+    ) -> Result<Option<dao::Credentials>, sqlx::Error> {
+        tracing::debug!("Auth repo: Finding credentials of {email}");
+        let _timer = telemetry::query_timer("auth", "find_credentials");
+        sqlx::query_as::<_, dao::Credentials>(
+            "SELECT id, password, role, active, locked_at,
+                totp_enabled_at IS NOT NULL AS totp_enabled, locale
+            FROM employee
+            WHERE email = $1",
+        )
+        .bind(email)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Auth repo: find_credentials_by_id", skip_all)]
+    pub async fn find_credentials_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<dao::Credentials>, sqlx::Error> {
+        tracing::debug!("Auth repo: Finding credentials of employee {id}");
+        let _timer = telemetry::query_timer("auth", "find_credentials_by_id");
+        sqlx::query_as::<_, dao::Credentials>(
+            "SELECT id, password, role, active, locked_at,
+                totp_enabled_at IS NOT NULL AS totp_enabled, locale
+            FROM employee
+            WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Auth repo: find_totp", skip_all)]
+    pub async fn find_totp(&self, id: i64) -> Result<Option<dao::Totp>, sqlx::Error> {
+        tracing::debug!("Auth repo: Finding TOTP state of employee {id}");
+        let _timer = telemetry::query_timer("auth", "find_totp");
+        sqlx::query_as::<_, dao::Totp>(
+            "SELECT email, totp_secret AS secret, totp_enabled_at IS NOT NULL AS enabled
+            FROM employee
+            WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Сохраняет новый секрет, пока 2FA не подключена. Возвращает `false`, если уже подключена.
+    #[tracing::instrument(name = "Auth repo: set_totp_secret", skip_all)]
+    pub async fn set_totp_secret(&self, id: i64, secret: &str) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Auth repo: Setting TOTP secret of employee {id}");
+        let _timer = telemetry::query_timer("auth", "set_totp_secret");
+        sqlx::query(
+            "UPDATE employee SET totp_secret = $2, totp_last_step = NULL
+            WHERE id = $1 AND totp_enabled_at IS NULL",
+        )
+        .bind(id)
+        .bind(secret)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Запоминает использованный шаг TOTP. Возвращает `false`, если код этого или
+    /// более позднего шага уже принимался.
+    #[tracing::instrument(name = "Auth repo: use_totp_step", skip_all)]
+    pub async fn use_totp_step(&self, id: i64, step: i64) -> Result<bool, sqlx::Error> {
+        let _timer = telemetry::query_timer("auth", "use_totp_step");
+        sqlx::query(
+            "UPDATE employee SET totp_last_step = $2
+            WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)",
+        )
+        .bind(id)
+        .bind(step)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Гасит код восстановления. Возвращает `false`, если такого неиспользованного нет.
+    #[tracing::instrument(name = "Auth repo: use_recovery_code", skip_all)]
+    pub async fn use_recovery_code(&self, id: i64, code_hash: &str) -> Result<bool, sqlx::Error> {
+        let _timer = telemetry::query_timer("auth", "use_recovery_code");
+        sqlx::query(
+            "UPDATE recovery_code SET used_at = CURRENT_TIMESTAMP
+            WHERE employee_id = $1 AND code_hash = $2 AND used_at IS NULL",
+        )
+        .bind(id)
+        .bind(code_hash)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Подключает 2FA и заменяет коды восстановления.
+    #[tracing::instrument(name = "Auth repo: enable_totp", skip_all)]
+    pub async fn enable_totp(&self, id: i64, code_hashes: &[String]) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Enabling TOTP of employee {id}");
+        let _timer = telemetry::query_timer("auth", "enable_totp");
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE employee SET totp_enabled_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+        sqlx::query("DELETE FROM recovery_code WHERE employee_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+        sqlx::query(
+            "INSERT INTO recovery_code (employee_id, code_hash) SELECT $1, unnest($2::text[])",
+        )
+        .bind(id)
+        .bind(code_hashes)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        tx.commit().await
+    }
+
+    #[tracing::instrument(name = "Auth repo: disable_totp", skip_all)]
+    pub async fn disable_totp(&self, id: i64) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Disabling TOTP of employee {id}");
+        let _timer = telemetry::query_timer("auth", "disable_totp");
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE employee
+            SET totp_secret = NULL, totp_enabled_at = NULL, totp_last_step = NULL
+            WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        sqlx::query("DELETE FROM recovery_code WHERE employee_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+        tx.commit().await
+    }
+
+    /// Увеличивает счётчик неудачных входов и блокирует учётную запись на `threshold`-й попытке.
+    /// Возвращает `true`, если запись заблокирована этим вызовом.
+    #[tracing::instrument(name = "Auth repo: register_failed_login", skip_all)]
+    pub async fn register_failed_login(
+        &self,
+        id: i64,
+        threshold: u32,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Auth repo: Registering failed login of employee {id}");
+        let _timer = telemetry::query_timer("auth", "register_failed_login");
+        sqlx::query_scalar(
+            "UPDATE employee
+            SET failed_logins = failed_logins + 1,
+                locked_at = CASE WHEN failed_logins + 1 >= $2 THEN COALESCE(locked_at, CURRENT_TIMESTAMP) ELSE NULL END
+            WHERE id = $1
+            RETURNING locked_at IS NOT NULL",
+        )
+        .bind(id)
+        .bind(threshold as i32)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Auth repo: reset_failed_logins", skip_all)]
+    pub async fn reset_failed_logins(&self, id: i64) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Resetting failed logins of employee {id}");
+        let _timer = telemetry::query_timer("auth", "reset_failed_logins");
+        sqlx::query("UPDATE employee SET failed_logins = 0 WHERE id = $1 AND failed_logins > 0")
+            .bind(id)
+            .execute(&*self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    #[tracing::instrument(name = "Auth repo: update_password", skip_all)]
+    pub async fn update_password(&self, id: i64, password: &str) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Updating password hash of employee {id}");
+        let _timer = telemetry::query_timer("auth", "update_password");
+        sqlx::query("UPDATE employee SET password = $2 WHERE id = $1")
+            .bind(id)
+            .bind(password)
+            .execute(&*self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    #[tracing::instrument(name = "Auth repo: find_password", skip_all)]
+    pub async fn find_password(&self, id: i64) -> Result<Option<String>, sqlx::Error> {
+        tracing::debug!("Auth repo: Finding password hash of employee {id}");
+        let _timer = telemetry::query_timer("auth", "find_password");
+        sqlx::query_scalar("SELECT password FROM employee WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Сохраняет токен восстановления, прежние неиспользованные токены сотрудника гасятся.
+    #[tracing::instrument(name = "Auth repo: create_password_reset", skip_all)]
+    pub async fn create_password_reset(
+        &self,
+        employee_id: i64,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Creating password reset token of employee {employee_id}");
+        let _timer = telemetry::query_timer("auth", "create_password_reset");
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE password_reset SET used_at = CURRENT_TIMESTAMP
+            WHERE employee_id = $1 AND used_at IS NULL",
+        )
+        .bind(employee_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        sqlx::query(
+            "INSERT INTO password_reset (employee_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+        )
+        .bind(employee_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        tx.commit().await
+    }
+
+    /// Гасит действующий токен и в той же транзакции меняет пароль, снимает счётчик
+    /// неудачных входов и завершает все сессии. Возвращает id сотрудника или `None`, если токен неизвестен,
+    /// использован или истёк.
+    #[tracing::instrument(name = "Auth repo: reset_password", skip_all)]
+    pub async fn reset_password(
+        &self,
+        token_hash: &str,
+        password: &str,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        tracing::debug!("Auth repo: Resetting password by token");
+        let _timer = telemetry::query_timer("auth", "reset_password");
+        let mut tx = self.pool.begin().await?;
+        let employee_id: Option<i64> = sqlx::query_scalar(
+            "UPDATE password_reset SET used_at = CURRENT_TIMESTAMP
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+            RETURNING employee_id",
+        )
+        .bind(token_hash)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        let Some(employee_id) = employee_id else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            "UPDATE employee SET password = $2, failed_logins = 0, locked_at = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1",
+        )
+        .bind(employee_id)
+        .bind(password)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        sqlx::query(
+            "UPDATE session SET revoked_at = CURRENT_TIMESTAMP
+            WHERE employee_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(employee_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        tx.commit().await?;
+        Ok(Some(employee_id))
+    }
+
+    #[tracing::instrument(name = "Auth repo: create_session", skip_all)]
+    pub async fn create_session(
+        &self,
+        employee_id: i64,
+        user_agent: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        tracing::debug!("Auth repo: Creating session of employee {employee_id}");
+        let _timer = telemetry::query_timer("auth", "create_session");
+        sqlx::query_scalar(
+            "INSERT INTO session (employee_id, user_agent, expires_at) VALUES ($1, $2, $3)
+            RETURNING id",
+        )
+        .bind(employee_id)
+        .bind(user_agent)
+        .bind(expires_at)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Auth repo: add_refresh_token", skip_all)]
+    pub async fn add_refresh_token(&self, session_id: i64, jti: &str) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Adding refresh token to session {session_id}");
+        let _timer = telemetry::query_timer("auth", "add_refresh_token");
+        sqlx::query("INSERT INTO refresh_token (jti, session_id) VALUES ($1, $2)")
+            .bind(jti)
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Заменяет refresh токен `jti` на `new_jti` и продлевает сессию до `expires_at`.
+    ///
+    /// Повторное предъявление уже использованного токена означает, что он украден,
+    /// поэтому сессия отзывается целиком.
+    #[tracing::instrument(name = "Auth repo: rotate_refresh_token", skip_all)]
+    pub async fn rotate_refresh_token(
+        &self,
+        session_id: i64,
+        jti: &str,
+        new_jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Rotation, sqlx::Error> {
+        tracing::debug!("Auth repo: Rotating refresh token of session {session_id}");
+        let _timer = telemetry::query_timer("auth", "rotate_refresh_token");
+        let mut tx = self.pool.begin().await?;
+        // Блокировка строки не даёт двум одновременным обновлениям получить по токену
+        let used: Option<bool> = sqlx::query_scalar(
+            "SELECT used_at IS NOT NULL FROM refresh_token
+            WHERE jti = $1 AND session_id = $2
+            FOR UPDATE",
+        )
+        .bind(jti)
+        .bind(session_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+
+        match used {
+            None => return Ok(Rotation::Invalid),
+            Some(true) => {
+                sqlx::query(
+                    "UPDATE session SET revoked_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND revoked_at IS NULL",
+                )
+                .bind(session_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| {
+                    tracing::error!("Database error: {err}");
+                    err
+                })?;
+                tx.commit().await?;
+                return Ok(Rotation::Reused);
+            }
+            Some(false) => {}
+        }
+
+        let role: Option<Role> = sqlx::query_scalar(
+            "UPDATE session s SET last_used_at = CURRENT_TIMESTAMP, expires_at = $2
+            FROM employee e
+            WHERE s.id = $1 AND e.id = s.employee_id
+                AND s.revoked_at IS NULL AND s.expires_at > CURRENT_TIMESTAMP AND e.active
+            RETURNING e.role",
+        )
+        .bind(session_id)
+        .bind(expires_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        let Some(role) = role else {
+            return Ok(Rotation::Invalid);
+        };
+
+        sqlx::query("UPDATE refresh_token SET used_at = CURRENT_TIMESTAMP WHERE jti = $1")
+            .bind(jti)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+        sqlx::query("INSERT INTO refresh_token (jti, session_id) VALUES ($1, $2)")
+            .bind(new_jti)
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+        tx.commit().await?;
+        Ok(Rotation::Rotated(role))
+    }
+
+    #[tracing::instrument(name = "Auth repo: set_locale", skip_all)]
+    pub async fn set_locale(&self, id: i64, locale: Option<&str>) -> Result<(), sqlx::Error> {
+        tracing::debug!("Auth repo: Setting locale of employee {id}");
+        let _timer = telemetry::query_timer("auth", "set_locale");
+        sqlx::query("UPDATE employee SET locale = $2 WHERE id = $1")
+            .bind(id)
+            .bind(locale)
+            .execute(&*self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Данные сотрудника для проверки доступа, если сессия действует, а сотрудник активен.
+    #[tracing::instrument(name = "Auth repo: find_session_owner", skip_all)]
+    pub async fn find_session_owner(
+        &self,
+        employee_id: i64,
+        session_id: i64,
+    ) -> Result<Option<dao::SessionOwner>, sqlx::Error> {
+        let _timer = telemetry::query_timer("auth", "find_session_owner");
+        sqlx::query_as::<_, dao::SessionOwner>(
+            "SELECT e.role, e.totp_enabled_at IS NOT NULL AS totp_enabled, e.locale
+            FROM session s
+            JOIN employee e ON e.id = s.employee_id
+            WHERE s.id = $2 AND s.employee_id = $1
+                AND s.revoked_at IS NULL AND s.expires_at > CURRENT_TIMESTAMP AND e.active",
+        )
+        .bind(employee_id)
+        .bind(session_id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Находит действующий API ключ по хешу и отмечает его использование.
+    /// Возвращает id ключа и коды его прав.
+    #[tracing::instrument(name = "Auth repo: use_api_key", skip_all)]
+    pub async fn use_api_key(
+        &self,
+        key_hash: &str,
+    ) -> Result<Option<(i64, Vec<String>)>, sqlx::Error> {
+        let _timer = telemetry::query_timer("auth", "use_api_key");
+        sqlx::query_as(
+            "UPDATE api_key SET last_used_at = CURRENT_TIMESTAMP
+            WHERE key_hash = $1 AND revoked_at IS NULL
+                AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+            RETURNING id, permissions",
+        )
+        .bind(key_hash)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Auth repo: get_sessions", skip_all)]
+    pub async fn get_sessions(&self, employee_id: i64) -> Result<Vec<dao::Session>, sqlx::Error> {
+        tracing::debug!("Auth repo: Getting sessions of employee {employee_id}");
+        let _timer = telemetry::query_timer("auth", "get_sessions");
+        sqlx::query_as::<_, dao::Session>(
+            "SELECT id, user_agent, created_at, last_used_at, expires_at
+            FROM session
+            WHERE employee_id = $1 AND revoked_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+            ORDER BY last_used_at DESC",
+        )
+        .bind(employee_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Отзывает сессии сотрудника: одну `only` или все, кроме `except`.
+    /// Возвращает число отозванных.
+    #[tracing::instrument(name = "Auth repo: revoke_sessions", skip_all)]
+    pub async fn revoke_sessions(
+        &self,
+        employee_id: i64,
+        only: Option<i64>,
+        except: Option<i64>,
+    ) -> Result<u64, sqlx::Error> {
+        tracing::debug!("Auth repo: Revoking sessions of employee {employee_id}");
+        let _timer = telemetry::query_timer("auth", "revoke_sessions");
+        sqlx::query(
+            "UPDATE session SET revoked_at = CURRENT_TIMESTAMP
+            WHERE employee_id = $1 AND revoked_at IS NULL
+                AND ($2::bigint IS NULL OR id = $2)
+                AND ($3::bigint IS NULL OR id <> $3)",
+        )
+        .bind(employee_id)
+        .bind(only)
+        .bind(except)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
+
+/// Результат `Repo::rotate_refresh_token`.
+pub enum Rotation {
+    Rotated(Role),
+    /// Токен уже обменивался, сессия отозвана.
+    Reused,
+    /// Токен неизвестен, сессия отозвана или истекла, сотрудник деактивирован.
+    Invalid,
+}
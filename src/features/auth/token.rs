@@ -0,0 +1,372 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::FromRequestParts;
+use axum::http::{HeaderMap, header, request::Parts};
+use axum::{Json, http::StatusCode};
+use chrono::Utc;
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::Repo;
+use crate::i18n;
+use crate::models::dao::{Permission, Role};
+use crate::models::dto;
+
+/// Время жизни access токена.
+pub const ACCESS_TOKEN_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Время жизни токена между вводом пароля и кода 2FA.
+pub const MFA_TOKEN_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Начало API ключа, по нему ключ отличается от JWT в заголовке `Authorization`.
+pub const API_KEY_PREFIX: &str = "mds_";
+
+/// Заголовок с API ключом для интеграций, которые не умеют `Authorization: Bearer`.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Ключи подписи access и refresh токенов (HS256).
+pub struct Keys {
+    access: (EncodingKey, DecodingKey),
+    refresh: (EncodingKey, DecodingKey),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    /// id сотрудника.
+    pub sub: String,
+    pub role: Role,
+    /// id сессии.
+    pub sid: i64,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefreshClaims {
+    /// id сотрудника.
+    pub sub: String,
+    /// id сессии.
+    pub sid: i64,
+    /// id токена в таблице `refresh_token`.
+    pub jti: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Подтверждает, что пароль уже проверен и остался код 2FA.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MfaClaims {
+    /// id сотрудника.
+    pub sub: String,
+    pub mfa: bool,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl Keys {
+    /// Один секрет для access и refresh токенов, отдельный задаётся `with_refresh_secret`.
+    pub fn new(secret: &str) -> Self {
+        Keys {
+            access: key_pair(secret),
+            refresh: key_pair(secret),
+        }
+    }
+
+    pub fn with_refresh_secret(mut self, secret: &str) -> Self {
+        self.refresh = key_pair(secret);
+        self
+    }
+
+    /// Случайный секрет на время жизни процесса: токены не переживут перезапуск.
+    pub fn random() -> Self {
+        let secret: [u8; 32] = rand::rng().random();
+        Self::new(&hex::encode(secret))
+    }
+
+    pub fn access_token(&self, id: i64, role: Role, session: i64) -> Result<String, dto::Error> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: id.to_string(),
+            role,
+            sid: session,
+            iat: now,
+            exp: now + ACCESS_TOKEN_TTL.as_secs() as i64,
+        };
+        jsonwebtoken::encode(&Header::default(), &claims, &self.access.0)
+            .map_err(|err| dto::Error::InternalServerError(format!("jwt error: {}", err)))
+    }
+
+    /// Refresh токен сессии со случайным `jti`. Возвращает токен и `jti`.
+    pub fn refresh_token(
+        &self,
+        id: i64,
+        session: i64,
+        ttl: Duration,
+    ) -> Result<(String, String), dto::Error> {
+        let now = Utc::now().timestamp();
+        let jti = hex::encode(rand::rng().random::<[u8; 16]>());
+        let claims = RefreshClaims {
+            sub: id.to_string(),
+            sid: session,
+            jti: jti.clone(),
+            iat: now,
+            exp: now + ttl.as_secs() as i64,
+        };
+        let token = jsonwebtoken::encode(&Header::default(), &claims, &self.refresh.0)
+            .map_err(|err| dto::Error::InternalServerError(format!("jwt error: {}", err)))?;
+        Ok((token, jti))
+    }
+
+    pub fn mfa_token(&self, id: i64) -> Result<String, dto::Error> {
+        let now = Utc::now().timestamp();
+        let claims = MfaClaims {
+            sub: id.to_string(),
+            mfa: true,
+            iat: now,
+            exp: now + MFA_TOKEN_TTL.as_secs() as i64,
+        };
+        jsonwebtoken::encode(&Header::default(), &claims, &self.access.0)
+            .map_err(|err| dto::Error::InternalServerError(format!("jwt error: {}", err)))
+    }
+
+    /// id сотрудника из токена `mfa_token`.
+    pub fn verify_mfa(&self, token: &str) -> Result<i64, dto::Error> {
+        jsonwebtoken::decode::<MfaClaims>(token, &self.access.1, &Validation::default())
+            .ok()
+            .filter(|data| data.claims.mfa)
+            .and_then(|data| data.claims.sub.parse().ok())
+            .ok_or_else(|| dto::Error::Unauthorized("Invalid or expired mfa token".to_string()))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, dto::Error> {
+        jsonwebtoken::decode::<Claims>(token, &self.access.1, &Validation::default())
+            .map(|data| data.claims)
+            .map_err(|_| dto::Error::Unauthorized("Invalid or expired token".to_string()))
+    }
+
+    pub fn verify_refresh(&self, token: &str) -> Result<RefreshClaims, dto::Error> {
+        jsonwebtoken::decode::<RefreshClaims>(token, &self.refresh.1, &Validation::default())
+            .map(|data| data.claims)
+            .map_err(|_| dto::Error::Unauthorized("Invalid or expired refresh token".to_string()))
+    }
+}
+
+fn key_pair(secret: &str) -> (EncodingKey, DecodingKey) {
+    (
+        EncodingKey::from_secret(secret.as_bytes()),
+        DecodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Проверка access токенов для `AuthEmployee`: подпись, а также что сессия не отозвана
+/// и сотрудник активен. Роль берётся из базы, поэтому её смена действует сразу.
+/// Язык, выбранный сотрудником, заменяет язык из `Accept-Language`.
+pub struct Authenticator {
+    keys: Arc<Keys>,
+    repo: Repo,
+    require_2fa: bool,
+}
+
+impl Authenticator {
+    pub fn new(pool: &sqlx::PgPool, keys: Arc<Keys>) -> Self {
+        Authenticator {
+            keys,
+            repo: Repo::new(Arc::new(pool.clone())),
+            require_2fa: false,
+        }
+    }
+
+    /// Менеджеры и суперадмины без 2FA проходят аутентификацию, но не проверку ролей,
+    /// пока не подключат её.
+    pub fn with_required_2fa(mut self, require_2fa: bool) -> Self {
+        self.require_2fa = require_2fa;
+        self
+    }
+
+    pub async fn authenticate(&self, token: &str) -> Result<AuthEmployee, dto::Error> {
+        let claims = self.keys.verify(token)?;
+        let id = claims
+            .sub
+            .parse()
+            .map_err(|_| dto::Error::Unauthorized("Invalid or expired token".to_string()))?;
+        let owner = self
+            .repo
+            .find_session_owner(id, claims.sid)
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(|| {
+                dto::Error::Unauthorized(
+                    "Session is revoked or employee is deactivated".to_string(),
+                )
+            })?;
+        if let Some(locale) = owner.locale.and_then(|locale| locale.parse().ok()) {
+            i18n::prefer(locale);
+        }
+        Ok(AuthEmployee {
+            id,
+            role: owner.role,
+            session: claims.sid,
+            needs_2fa: self.require_2fa && owner.role.is_privileged() && !owner.totp_enabled,
+        })
+    }
+
+    /// Проверяет API ключ: известен, не отозван и не истёк. Отмечает время использования.
+    pub async fn authenticate_api_key(&self, key: &str) -> Result<Principal, dto::Error> {
+        let (id, permissions) = self
+            .repo
+            .use_api_key(&digest(key))
+            .await
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))?
+            .ok_or_else(|| dto::Error::Unauthorized("Invalid or expired API key".to_string()))?;
+        Ok(Principal::ApiKey {
+            id,
+            // Права, удалённые из кода, просто перестают действовать
+            permissions: permissions
+                .iter()
+                .filter_map(|code| Permission::from(code).ok())
+                .collect(),
+        })
+    }
+}
+
+/// SHA-256 случайного токена в hex. Токены длинные и случайные, поэтому соль не нужна.
+pub fn digest(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Сотрудник из заголовка `Authorization: Bearer <token>`.
+///
+/// Проверка берётся из расширений запроса, поэтому приложение оборачивается
+/// `Extension(Arc<Authenticator>)`.
+///
+/// ```ignore
+/// async fn handler(auth: AuthEmployee) -> ... {
+///     auth.require(&[Role::Superadmin])?;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AuthEmployee {
+    pub id: i64,
+    pub role: Role,
+    /// id сессии, которой выдан токен.
+    pub session: i64,
+    /// 2FA обязательна для роли, но не подключена: доступно только её подключение.
+    pub needs_2fa: bool,
+}
+
+impl AuthEmployee {
+    pub fn require(&self, roles: &[Role]) -> Result<(), dto::Error> {
+        if self.needs_2fa {
+            Err(two_factor_required())
+        } else if roles.contains(&self.role) {
+            Ok(())
+        } else {
+            Err(dto::Error::Forbidden("Not enough permissions".to_string()))
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthEmployee {
+    type Rejection = (StatusCode, Json<dto::ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let authenticator = parts
+            .extensions
+            .get::<Arc<Authenticator>>()
+            .cloned()
+            .ok_or_else(|| {
+                tracing::error!("Authenticator is not installed as a request extension");
+                dto::Error::InternalServerError("Authentication is not configured".to_string())
+                    .into_response()
+            })?;
+
+        let token = bearer(&parts.headers).ok_or_else(|| {
+            dto::Error::Unauthorized("Missing bearer token".to_string()).into_response()
+        })?;
+        authenticator
+            .authenticate(token)
+            .await
+            .map_err(dto::Error::into_response)
+    }
+}
+
+/// Сотрудник по access токену в `Authorization: Bearer ...` или интеграция по API
+/// ключу в `X-Api-Key` либо там же в `Authorization`.
+///
+/// ```ignore
+/// async fn handler(principal: Principal) -> ... {
+///     principal.require(Permission::RequestsCreate)?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub enum Principal {
+    Employee(AuthEmployee),
+    ApiKey {
+        id: i64,
+        permissions: Vec<Permission>,
+    },
+}
+
+impl Principal {
+    /// Сотруднику право даёт роль, ключу - список прав, выданный при создании.
+    pub fn require(&self, permission: Permission) -> Result<(), dto::Error> {
+        let allowed = match self {
+            Principal::Employee(employee) if employee.needs_2fa => {
+                return Err(two_factor_required());
+            }
+            Principal::Employee(employee) => permission.roles().contains(&employee.role),
+            Principal::ApiKey { permissions, .. } => permissions.contains(&permission),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(dto::Error::Forbidden("Not enough permissions".to_string()))
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Principal {
+    type Rejection = (StatusCode, Json<dto::ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let key = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .or_else(|| bearer(&parts.headers).filter(|token| token.starts_with(API_KEY_PREFIX)));
+        let Some(key) = key else {
+            return AuthEmployee::from_request_parts(parts, state)
+                .await
+                .map(Principal::Employee);
+        };
+
+        let authenticator = parts
+            .extensions
+            .get::<Arc<Authenticator>>()
+            .cloned()
+            .ok_or_else(|| {
+                tracing::error!("Authenticator is not installed as a request extension");
+                dto::Error::InternalServerError("Authentication is not configured".to_string())
+                    .into_response()
+            })?;
+        authenticator
+            .authenticate_api_key(key)
+            .await
+            .map_err(dto::Error::into_response)
+    }
+}
+
+fn two_factor_required() -> dto::Error {
+    dto::Error::Forbidden("Two-factor authentication must be enabled for this role".to_string())
+}
+
+fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
@@ -0,0 +1,78 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::models::dto;
+
+/// Длительность шага TOTP в секундах, как в Google Authenticator и аналогах.
+const STEP: u64 = 30;
+
+/// Сколько кодов восстановления выдаётся при подключении 2FA.
+pub const RECOVERY_CODES: usize = 10;
+
+/// Новый секрет TOTP (160 бит) в base32.
+pub fn generate_secret() -> String {
+    let secret: [u8; 20] = rand::rng().random();
+    Secret::Raw(secret.to_vec()).to_encoded().to_string()
+}
+
+/// Ссылка `otpauth://` для QR-кода в приложении-аутентификаторе.
+pub fn provisioning_uri(secret: &str, issuer: &str, account: &str) -> Result<String, dto::Error> {
+    Ok(totp(secret, issuer, account)?.get_url())
+}
+
+/// Шаг, которому соответствует `code`, с допуском в один шаг в обе стороны
+/// на расхождение часов. `None` - код неверен.
+pub fn matching_step(secret: &str, code: &str) -> Result<Option<i64>, dto::Error> {
+    let totp = totp(secret, "", "")?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| dto::Error::InternalServerError(format!("Clock error: {err}")))?
+        .as_secs();
+    let current = now / STEP;
+    Ok([current, current - 1, current + 1]
+        .into_iter()
+        .find(|step| totp.check(code.trim(), step * STEP))
+        .map(|step| step as i64))
+}
+
+/// Коды восстановления вида `xxxx-xxxx-xxxx-xxxx`, 64 случайных бита каждый.
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODES)
+        .map(|_| {
+            let code = hex::encode(rand::rng().random::<[u8; 8]>());
+            code.as_bytes()
+                .chunks(4)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect()
+}
+
+/// Код восстановления без дефисов, пробелов и регистра, в том виде, в каком хешируется.
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn totp(secret: &str, issuer: &str, account: &str) -> Result<TOTP, dto::Error> {
+    let bytes = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|err| dto::Error::InternalServerError(format!("TOTP secret error: {err:?}")))?;
+    // Проверка кода не зависит от issuer, поэтому пустой заменяется на None
+    let issuer = (!issuer.is_empty()).then(|| issuer.to_string());
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        STEP,
+        bytes,
+        issuer,
+        account.to_string(),
+    )
+    .map_err(|err| dto::Error::InternalServerError(format!("TOTP error: {err}")))
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::models::dto::Category;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn create_category(
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<Category>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: create_category", payload = ?payload);
+        async {
+            match handler.logic.create(payload).await {
+                Ok(result) => {
+                    tracing::debug!("Category created successfully: {:?}", result);
+                    (StatusCode::CREATED, Json(json!(result)))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to create category: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_categories(State(handler): State<Arc<Handler>>) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: get_categories");
+        async {
+            match handler.logic.get_all().await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get categories");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_tree(State(handler): State<Arc<Handler>>) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: get_tree");
+        async {
+            match handler.logic.get_tree().await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get category tree");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_category_by_id(
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: get_category_by_id with ", id);
+        async {
+            match handler.logic.get_by_id(id).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get category by id");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn update_category(
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+        Json(payload): Json<Category>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: update_category with ", id);
+        async {
+            match handler.logic.put_by_id(id, payload).await {
+                Ok(result) => {
+                    tracing::debug!("Put category by id successfully");
+                    (StatusCode::OK, Json(json!(result)))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to put category by id: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn delete_category(
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Category handler: delete_category with ", id);
+        async {
+            match handler.logic.delete_by_id(id).await {
+                Ok(result) => (StatusCode::OK, Json(json!({"id": result}))),
+                Err(err) => {
+                    tracing::error!("Failed to delete category by id: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::repo::{Repo, Update};
+use crate::models::dao;
+use crate::models::dto::{Category, CategoryNode, Error, Service};
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    fn validate(payload: &Category) -> Result<(), Error> {
+        if payload.name.trim().is_empty() {
+            tracing::error!("Field name is empty");
+            return Err(Error::BadRequest(
+                "Field 'name' can't be empty.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Category logic: create", skip_all)]
+    pub async fn create(&self, payload: Category) -> Result<Category, Error> {
+        tracing::debug!("Category logic: Creating category");
+        Self::validate(&payload)?;
+        self.repo
+            .create(&payload.name, payload.parent_id)
+            .await
+            .map(dao::Category::to_dto)
+            .map_err(|err| write_error(err, payload.parent_id))
+    }
+
+    #[tracing::instrument(name = "Category logic: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<Category>, Error> {
+        tracing::debug!("Category logic: Getting all categories");
+        self.repo
+            .get_all()
+            .await
+            .map(|rows| rows.into_iter().map(dao::Category::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    /// Дерево каталога от корневых категорий. Услуги без категории в дерево не входят.
+    #[tracing::instrument(name = "Category logic: get_tree", skip_all)]
+    pub async fn get_tree(&self) -> Result<Vec<CategoryNode>, Error> {
+        tracing::debug!("Category logic: Building category tree");
+        let internal = |_| Error::InternalServerError("Internal database error".to_string());
+        let categories = self.repo.get_all().await.map_err(internal)?;
+        let services = self.repo.get_services().await.map_err(internal)?;
+
+        let mut children: HashMap<Option<i64>, Vec<dao::Category>> = HashMap::new();
+        for category in categories {
+            children
+                .entry(category.parent_id)
+                .or_default()
+                .push(category);
+        }
+        let mut services_by_category: HashMap<i64, Vec<Service>> = HashMap::new();
+        for service in services.into_iter().map(dao::Service::to_dto) {
+            if let Some(category_id) = service.category_id.flatten() {
+                services_by_category
+                    .entry(category_id)
+                    .or_default()
+                    .push(service);
+            }
+        }
+
+        Ok(build_nodes(None, &mut children, &mut services_by_category))
+    }
+
+    #[tracing::instrument(name = "Category logic: get_by_id", skip_all)]
+    pub async fn get_by_id(&self, id: i64) -> Result<Category, Error> {
+        tracing::debug!("Category logic: Getting category by id");
+        match self.repo.get_by_id(id).await {
+            Ok(Some(category)) => Ok(dao::Category::to_dto(category)),
+            Ok(None) => Err(Error::NotFound(format!(
+                "Category with id: {} not found",
+                id
+            ))),
+            Err(_) => Err(Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+
+    /// Переименование и перенос категории. Перенос в саму себя или в свою
+    /// подкатегорию отклоняется.
+    #[tracing::instrument(name = "Category logic: put_by_id", skip_all)]
+    pub async fn put_by_id(&self, id: i64, payload: Category) -> Result<Category, Error> {
+        tracing::debug!("Category logic: Updating category by id");
+        Self::validate(&payload)?;
+        let result = self
+            .repo
+            .update_by_id(id, &payload.name, payload.parent_id)
+            .await
+            .map_err(|err| write_error(err, payload.parent_id))?;
+
+        match result {
+            Update::Updated(category) => Ok(dao::Category::to_dto(category)),
+            Update::NotFound => Err(Error::NotFound(format!(
+                "Category with id: {} not found",
+                id
+            ))),
+            Update::Cycle => {
+                tracing::error!(
+                    "Category {} can't be moved under {:?}",
+                    id,
+                    payload.parent_id
+                );
+                Err(Error::BadRequest(
+                    "Category can't be moved into itself or its subcategory.".to_string(),
+                ))
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "Category logic: delete_by_id", skip_all)]
+    pub async fn delete_by_id(&self, id: i64) -> Result<i64, Error> {
+        tracing::debug!("Category logic: Deleting category by id");
+        match self.repo.delete_by_id(id).await {
+            Ok(rows) if rows > 0 => Ok(id),
+            Ok(_) => Err(Error::NotFound(format!(
+                "Category with id: {} not found",
+                id
+            ))),
+            Err(err) if is_foreign_key_violation(&err) => Err(Error::Conflict(
+                "Category has subcategories, delete or move them first.".to_string(),
+            )),
+            Err(_) => Err(Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+}
+
+/// Узлы с родителем `parent_id`, рекурсивно. Циклы исключены проверкой при переносе.
+fn build_nodes(
+    parent_id: Option<i64>,
+    children: &mut HashMap<Option<i64>, Vec<dao::Category>>,
+    services: &mut HashMap<i64, Vec<Service>>,
+) -> Vec<CategoryNode> {
+    children
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|category| CategoryNode {
+            id: category.id,
+            children: build_nodes(Some(category.id), children, services),
+            services: services.remove(&category.id).unwrap_or_default(),
+            name: category.name,
+        })
+        .collect()
+}
+
+fn is_foreign_key_violation(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .is_some_and(|err| err.is_foreign_key_violation())
+}
+
+/// Ошибка записи категории: неизвестный родитель или повтор названия.
+fn write_error(err: sqlx::Error, parent_id: Option<i64>) -> Error {
+    if is_foreign_key_violation(&err) {
+        return Error::BadRequest(format!(
+            "Parent category with id: {} not found",
+            parent_id.unwrap_or_default()
+        ));
+    }
+    if err
+        .as_database_error()
+        .is_some_and(|err| err.is_unique_violation())
+    {
+        return Error::Conflict(
+            "Category with this name already exists in the parent category.".to_string(),
+        );
+    }
+    Error::InternalServerError("Internal database error".to_string())
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::features::categories::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+/// Категории каталога услуг с вложенностью. Услуги привязываются к категориям
+/// через `features::services`.
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/categories", post(Handler::create_category))
+        .route("/categories", get(Handler::get_categories))
+        .route("/categories/tree", get(Handler::get_tree))
+        .route("/categories/{id}", get(Handler::get_category_by_id))
+        .route("/categories/{id}", put(Handler::update_category))
+        .route("/categories/{id}", delete(Handler::delete_category))
+        .with_state(handler)
+}
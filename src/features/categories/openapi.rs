@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI. Обработчики - методы `Handler`,
+// а `utoipa::path` работает только со свободными функциями, поэтому здесь заглушки.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{Category, CategoryNode, ErrorResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_category,
+        get_categories,
+        get_tree,
+        get_category_by_id,
+        update_category,
+        delete_category
+    ),
+    components(schemas(Category, CategoryNode, ErrorResponse)),
+    tags((name = "categories", description = "Категории каталога услуг"))
+)]
+pub struct ApiDoc;
+
+/// Создание категории
+#[utoipa::path(
+    post,
+    path = "/categories",
+    tag = "categories",
+    request_body = Category,
+    responses(
+        (status = 201, description = "Категория создана", body = Category),
+        (status = 400, description = "Пустое название или неизвестный родитель", body = ErrorResponse),
+        (status = 409, description = "У родителя уже есть категория с таким названием", body = ErrorResponse),
+        (status = 422, description = "Некорректное тело запроса")
+    )
+)]
+fn create_category() {}
+
+/// Список всех категорий без вложенности
+#[utoipa::path(
+    get,
+    path = "/categories",
+    tag = "categories",
+    responses(
+        (status = 200, description = "Список категорий", body = Vec<Category>),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn get_categories() {}
+
+/// Дерево каталога: категории с подкатегориями и услугами
+#[utoipa::path(
+    get,
+    path = "/categories/tree",
+    tag = "categories",
+    responses(
+        (status = 200, description = "Корневые категории", body = Vec<CategoryNode>),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn get_tree() {}
+
+/// Категория по id
+#[utoipa::path(
+    get,
+    path = "/categories/{id}",
+    tag = "categories",
+    params(("id" = i64, Path, description = "Id категории")),
+    responses(
+        (status = 200, description = "Категория", body = Category),
+        (status = 404, description = "Категория не найдена", body = ErrorResponse)
+    )
+)]
+fn get_category_by_id() {}
+
+/// Переименование и перенос категории
+#[utoipa::path(
+    put,
+    path = "/categories/{id}",
+    tag = "categories",
+    params(("id" = i64, Path, description = "Id категории")),
+    request_body = Category,
+    responses(
+        (status = 200, description = "Категория изменена", body = Category),
+        (status = 400, description = "Пустое название, неизвестный родитель или перенос в свою подкатегорию", body = ErrorResponse),
+        (status = 404, description = "Категория не найдена", body = ErrorResponse),
+        (status = 409, description = "У родителя уже есть категория с таким названием", body = ErrorResponse)
+    )
+)]
+fn update_category() {}
+
+/// Удаление категории. Её услуги остаются без категории
+#[utoipa::path(
+    delete,
+    path = "/categories/{id}",
+    tag = "categories",
+    params(("id" = i64, Path, description = "Id категории")),
+    responses(
+        (status = 200, description = "Id удалённой категории", body = Object, example = json!({"id": 1})),
+        (status = 404, description = "Категория не найдена", body = ErrorResponse),
+        (status = 409, description = "У категории есть подкатегории", body = ErrorResponse)
+    )
+)]
+fn delete_category() {}
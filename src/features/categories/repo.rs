@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::models::dao::{Category, Service};
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "Category repo: create", skip_all)]
+    pub async fn create(
+        &self,
+        name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<Category, sqlx::Error> {
+        tracing::debug!("Category repo: Adding category with name: {}", name);
+        let _timer = telemetry::query_timer("categories", "create");
+        sqlx::query_as::<_, Category>(
+            "INSERT INTO category (name, parent_id)
+            VALUES ($1, $2)
+            RETURNING *",
+        )
+        .bind(name)
+        .bind(parent_id)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Category repo: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<Category>, sqlx::Error> {
+        tracing::debug!("Category repo: Getting vector categories");
+        let _timer = telemetry::query_timer("categories", "get_all");
+        sqlx::query_as::<_, Category>("SELECT * FROM category ORDER BY name, id")
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    #[tracing::instrument(name = "Category repo: get_by_id", skip_all)]
+    pub async fn get_by_id(&self, id: i64) -> Result<Option<Category>, sqlx::Error> {
+        tracing::debug!("Category repo: Getting category by id = {}", id);
+        let _timer = telemetry::query_timer("categories", "get_by_id");
+        sqlx::query_as::<_, Category>("SELECT * FROM category WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Услуги, отнесённые к какой-либо категории, для дерева каталога.
+    #[tracing::instrument(name = "Category repo: get_services", skip_all)]
+    pub async fn get_services(&self) -> Result<Vec<Service>, sqlx::Error> {
+        tracing::debug!("Category repo: Getting categorized services");
+        let _timer = telemetry::query_timer("categories", "get_services");
+        sqlx::query_as::<_, Service>(
+            "SELECT * FROM service WHERE category_id IS NOT NULL ORDER BY name",
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Переименование и перенос категории.
+    ///
+    /// Таблица блокируется от параллельных изменений до конца транзакции, иначе два
+    /// встречных переноса могли бы пройти проверку и вместе образовать цикл.
+    #[tracing::instrument(name = "Category repo: update_by_id", skip_all)]
+    pub async fn update_by_id(
+        &self,
+        id: i64,
+        name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<Update, sqlx::Error> {
+        tracing::debug!("Category repo: Updating category by id = {}", id);
+        let _timer = telemetry::query_timer("categories", "update_by_id");
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("LOCK TABLE category IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+
+        if let Some(parent_id) = parent_id {
+            // Цикл появится, если категория окажется среди предков нового родителя
+            let cycle: bool = sqlx::query_scalar(
+                "WITH RECURSIVE ancestors AS (
+                    SELECT id, parent_id FROM category WHERE id = $1
+                    UNION ALL
+                    SELECT c.id, c.parent_id FROM category c
+                    JOIN ancestors a ON c.id = a.parent_id
+                )
+                SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2)",
+            )
+            .bind(parent_id)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+            if cycle {
+                return Ok(Update::Cycle);
+            }
+        }
+
+        let row = sqlx::query_as::<_, Category>(
+            "UPDATE category SET name = $1, parent_id = $2, updated_at = NOW()
+            WHERE id = $3
+            RETURNING *",
+        )
+        .bind(name)
+        .bind(parent_id)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        tx.commit().await?;
+
+        Ok(row.map_or(Update::NotFound, Update::Updated))
+    }
+
+    /// Удаление категории. Услуги остаются без категории, категория с
+    /// подкатегориями не удаляется (ограничение внешнего ключа).
+    #[tracing::instrument(name = "Category repo: delete_by_id", skip_all)]
+    pub async fn delete_by_id(&self, id: i64) -> Result<u64, sqlx::Error> {
+        tracing::debug!("Category repo: Deleting category by id = {}", id);
+        let _timer = telemetry::query_timer("categories", "delete_by_id");
+        let result = sqlx::query("DELETE FROM category WHERE id = $1")
+            .bind(id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Результат `Repo::update_by_id`.
+#[derive(Debug)]
+pub enum Update {
+    Updated(Category),
+    NotFound,
+    /// Новый родитель - сама категория или её потомок.
+    Cycle,
+}
@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
-use axum::{Json, extract::State, http::StatusCode};
+use axum::extract::{Path, State};
+use axum::{Json, http::StatusCode};
+use tracing::Instrument;
 
-use crate::models::dto::{Employee, ErrorResponse};
+use crate::features::auth::AuthEmployee;
+use crate::models::dao::Role;
+use crate::models::dto::{Employee, ErrorResponse, RoleName};
 
 pub struct Handler {
     logic: Arc<super::Logic>,
@@ -10,26 +14,69 @@ pub struct Handler {
 
 impl Handler {
     pub fn new(logic: Arc<super::Logic>) -> Self {
-        Handler { logic: logic }
+        Handler { logic }
     }
 
+    /// Создание сотрудника, только для суперадмина.
     pub async fn create(
+        auth: AuthEmployee,
         State(handler): State<Arc<Handler>>,
         Json(payload): Json<Employee>,
     ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-        tracing::info_span!("Employee handler: create", payload = ?payload)
-            .in_scope(|| async {
-                match handler.logic.create_employee(payload).await {
-                    Ok(_) => {
-                        tracing::debug!("Employee created successfully");
-                        Ok(StatusCode::CREATED)
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to create service: {:?}", err);
-                        Err(err.into_response())
-                    }
+        // Пароль в span не попадает
+        let span = tracing::info_span!(
+            "Employee handler: create",
+            email = ?payload.email,
+            role = ?payload.role,
+            by = auth.id
+        );
+        async {
+            auth.require(&[Role::Superadmin])
+                .map_err(|err| err.into_response())?;
+            match handler.logic.create_employee(payload).await {
+                Ok(_) => {
+                    tracing::debug!("Employee created successfully");
+                    Ok(StatusCode::CREATED)
                 }
-            })
-            .await
+                Err(err) => {
+                    tracing::error!("Failed to create service: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Снятие блокировки после неудачных входов, только для суперадмина.
+    pub async fn unlock(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+        let span = tracing::info_span!("Employee handler: unlock", id, by = auth.id);
+        async {
+            auth.require(&[Role::Superadmin])
+                .map_err(|err| err.into_response())?;
+            match handler.logic.unlock_employee(id).await {
+                Ok(_) => {
+                    tracing::info!("Employee {id} unlocked by {}", auth.id);
+                    Ok(StatusCode::NO_CONTENT)
+                }
+                Err(err) => {
+                    tracing::error!("Failed to unlock employee: {:?}", err);
+                    Err(err.into_response())
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Коды ролей и их названия для интерфейса.
+    pub async fn get_roles(
+        State(handler): State<Arc<Handler>>,
+    ) -> (StatusCode, Json<Vec<RoleName>>) {
+        (StatusCode::OK, Json(handler.logic.get_roles()))
     }
 }
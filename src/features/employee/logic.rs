@@ -1,8 +1,7 @@
 use std::sync::Arc;
 
-use bcrypt::hash;
-
 use crate::models::{dao, dto};
+use crate::password;
 
 pub struct Logic {
     repo: Arc<super::Repo>,
@@ -10,28 +9,38 @@ pub struct Logic {
 
 impl Logic {
     pub fn new(repo: Arc<super::Repo>) -> Self {
-        Logic { repo: repo }
+        Logic { repo }
     }
 
-    pub async fn create_employee(&self, payload: dto::Employee) -> Result<(), dto::Error> {
-        tracing::debug!("Employee logic: Creating employee");
-
+    /// Проверяет обязательные поля, роль сотрудника и политику паролей. Используется также при импорте.
+    pub fn validate(payload: &dto::Employee) -> Result<dao::Role, dto::Error> {
         let required_fields = [
             &payload.name,
             &payload.last_name,
             &payload.email,
             &payload.password,
-            &payload.role,
         ];
 
-        if required_fields.iter().any(|field| field.is_none()) {
+        if required_fields.iter().any(|field| field.is_none()) || payload.role.is_none() {
             return Err(dto::Error::BadRequest("Some fields are empty".to_string()));
         }
 
-        let hash_password = hash(payload.password.unwrap(), 14)
-            .map_err(|err| dto::Error::InternalServerError(format!("bcrypt error: {}", err)))?;
+        let role = dao::Role::from(payload.role.as_deref().unwrap())?;
+        password::check_policy(payload.password.as_deref().unwrap())?;
+        Ok(role)
+    }
+
+    /// Роли с названиями для интерфейса.
+    pub fn get_roles(&self) -> Vec<dto::RoleName> {
+        dao::Role::ALL.iter().map(dao::Role::to_dto).collect()
+    }
+
+    #[tracing::instrument(name = "Employee logic: create_employee", skip_all)]
+    pub async fn create_employee(&self, payload: dto::Employee) -> Result<(), dto::Error> {
+        tracing::debug!("Employee logic: Creating employee");
 
-        let role = dao::Role::from(payload.role)?;
+        let role = Self::validate(&payload)?;
+        let hash_password = password::hash(payload.password.as_deref().unwrap()).await?;
 
         self.repo
             .create(
@@ -47,4 +56,75 @@ impl Logic {
 
         Ok(())
     }
+
+    /// Список сотрудников без хешей паролей.
+    #[tracing::instrument(name = "Employee logic: get_employees", skip_all)]
+    pub async fn get_employees(&self) -> Result<Vec<dto::Employee>, dto::Error> {
+        tracing::debug!("Employee logic: Getting employees");
+        self.repo
+            .get_all()
+            .await
+            .map(|employees| {
+                employees
+                    .into_iter()
+                    .map(|e| dto::Employee {
+                        password: None,
+                        ..dao::Employee::to_dto(e)
+                    })
+                    .collect()
+            })
+            .map_err(|_| dto::Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    /// Новый пароль также снимает блокировку и завершает все сессии сотрудника.
+    #[tracing::instrument(name = "Employee logic: reset_password", skip_all)]
+    pub async fn reset_password(&self, email: &str, password: &str) -> Result<(), dto::Error> {
+        tracing::debug!("Employee logic: Resetting password");
+        password::check_policy(password)?;
+
+        let hash_password = password::hash(password).await?;
+        match self.repo.update_password(email, &hash_password).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(dto::Error::NotFound("Employee not found".to_string())),
+            Err(_) => Err(dto::Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+
+    #[tracing::instrument(name = "Employee logic: deactivate_employee", skip_all)]
+    pub async fn deactivate_employee(&self, email: &str) -> Result<(), dto::Error> {
+        tracing::debug!("Employee logic: Deactivating employee");
+        match self.repo.set_active(email, false).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(dto::Error::NotFound("Employee not found".to_string())),
+            Err(_) => Err(dto::Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+
+    #[tracing::instrument(name = "Employee logic: unlock_employee", skip_all)]
+    pub async fn unlock_employee(&self, id: i64) -> Result<(), dto::Error> {
+        tracing::debug!("Employee logic: Unlocking employee");
+        match self.repo.unlock(id).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(dto::Error::NotFound("Employee not found".to_string())),
+            Err(_) => Err(dto::Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
+
+    #[tracing::instrument(name = "Employee logic: unlock_employee_by_email", skip_all)]
+    pub async fn unlock_employee_by_email(&self, email: &str) -> Result<(), dto::Error> {
+        tracing::debug!("Employee logic: Unlocking employee by email");
+        match self.repo.unlock_by_email(email).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(dto::Error::NotFound("Employee not found".to_string())),
+            Err(_) => Err(dto::Error::InternalServerError(
+                "Internal database error".to_string(),
+            )),
+        }
+    }
 }
@@ -1,23 +1,31 @@
 pub mod handler;
 pub mod logic;
+pub mod openapi;
 pub mod repo;
 
 use std::sync::Arc;
 
 use axum::Router;
-use axum::routing::post;
+use axum::routing::{get, post};
 
 use crate::features::employee::handler::Handler;
 use crate::features::employee::logic::Logic;
 use crate::features::employee::repo::Repo;
 
 pub fn new(pool: &sqlx::PgPool) -> Router {
+    with_read_pool(pool, pool)
+}
+
+/// Как `new`, но списки читаются из `read_pool`, а записи идут в `pool`.
+pub fn with_read_pool(pool: &sqlx::PgPool, read_pool: &sqlx::PgPool) -> Router {
     let pool = Arc::new(pool.clone());
-    let repo = Arc::new(Repo::new(pool));
+    let repo = Arc::new(Repo::new(pool).with_read_pool(Arc::new(read_pool.clone())));
     let logic = Arc::new(Logic::new(repo));
     let handler = Arc::new(Handler::new(logic));
 
     Router::new()
         .route("/employee", post(Handler::create))
+        .route("/employee/roles", get(Handler::get_roles))
+        .route("/employee/{id}/unlock", post(Handler::unlock))
         .with_state(handler)
 }
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{Employee, ErrorResponse, Role, RoleName};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create, get_roles, unlock),
+    components(schemas(Employee, Role, RoleName, ErrorResponse)),
+    tags((name = "employee", description = "Сотрудники"))
+)]
+pub struct ApiDoc;
+
+/// Создание сотрудника
+///
+/// Обязательны `name`, `last_name`, `email`, `password` и `role`. Роль передаётся кодом:
+/// `employee`, `manager` или `superadmin`. Доступно только суперадмину.
+#[utoipa::path(
+    post,
+    path = "/employee",
+    tag = "employee",
+    request_body = Employee,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Сотрудник создан"),
+        (status = 400, description = "Не заполнены поля, неизвестная роль или пароль не соответствует политике", body = ErrorResponse),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse),
+        (status = 422, description = "Некорректное тело запроса"),
+        (status = 409, description = "Сотрудник с таким email уже есть", body = ErrorResponse),
+        (status = 500, description = "Ошибка хеширования пароля", body = ErrorResponse)
+    )
+)]
+fn create() {}
+
+/// Роли сотрудников
+///
+/// Коды ролей, которые принимает и возвращает API, и их названия для интерфейса.
+#[utoipa::path(
+    get,
+    path = "/employee/roles",
+    tag = "employee",
+    responses(
+        (status = 200, description = "Роли в порядке старшинства", body = [RoleName])
+    )
+)]
+fn get_roles() {}
+
+/// Снятие блокировки входа
+///
+/// Сбрасывает счётчик неудачных входов. Доступно только суперадмину.
+#[utoipa::path(
+    post,
+    path = "/employee/{id}/unlock",
+    tag = "employee",
+    params(("id" = i64, Path, description = "Id сотрудника")),
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Блокировка снята"),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse),
+        (status = 404, description = "Сотрудник не найден", body = ErrorResponse)
+    )
+)]
+fn unlock() {}
@@ -1,19 +1,31 @@
 use std::sync::Arc;
 
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use crate::models::dao;
+use crate::telemetry;
 
 pub struct Repo {
     pool: Arc<PgPool>,
+    read_pool: Arc<PgPool>,
 }
 
 impl Repo {
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Repo { pool: pool }
+        Repo {
+            read_pool: pool.clone(),
+            pool,
+        }
     }
 
-    pub async fn create<'a>(
+    /// Пул для списков, обычно реплика. По умолчанию используется основной пул.
+    pub fn with_read_pool(mut self, read_pool: Arc<PgPool>) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    #[tracing::instrument(name = "Employee repo: create", skip_all)]
+    pub async fn create(
         &self,
         name: String,
         last_name: String,
@@ -21,8 +33,33 @@ impl Repo {
         email: String,
         password: String,
         role: dao::Role,
+    ) -> Result<(), sqlx::Error> {
+        let _timer = telemetry::query_timer("employee", "create");
+        Self::insert(
+            &*self.pool,
+            name,
+            last_name,
+            middle_name,
+            email,
+            password,
+            role,
+        )
+        .await
+    }
+
+    /// Вставка через любой исполнитель запросов, в том числе внутри транзакции.
+    #[tracing::instrument(name = "Employee repo: insert", skip_all)]
+    pub async fn insert<'e, E: PgExecutor<'e>>(
+        executor: E,
+        name: String,
+        last_name: String,
+        middle_name: Option<String>,
+        email: String,
+        password: String,
+        role: dao::Role,
     ) -> Result<(), sqlx::Error> {
         tracing::debug!("Employee repo: Adding employee");
+        let _timer = telemetry::query_timer("employee", "insert");
         sqlx::query(
             "INSERT INTO employee (name, last_name, middle_name, email, password, role, active)
 			VALUES ($1, $2, $3, $4, $5, $6, $7)",
@@ -34,7 +71,7 @@ impl Repo {
         .bind(password)
         .bind(role)
         .bind(true)
-        .execute(&*self.pool)
+        .execute(executor)
         .await
         .map_err(|err| {
             tracing::error!("Database error: {err}");
@@ -43,4 +80,131 @@ impl Repo {
 
         Ok(())
     }
+
+    #[tracing::instrument(name = "Employee repo: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<dao::Employee>, sqlx::Error> {
+        tracing::debug!("Employee repo: Getting all employees");
+        let _timer = telemetry::query_timer("employee", "get_all");
+        sqlx::query_as::<_, dao::Employee>(
+            "SELECT id, name, last_name, middle_name, email, password, role,
+                active, created_at, updated_at
+            FROM employee
+            ORDER BY id",
+        )
+        .fetch_all(&*self.read_pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// В одной транзакции с новым паролем снимает блокировку после неудачных входов
+    /// и завершает все сессии. Возвращает `false`, если сотрудника с таким email нет.
+    #[tracing::instrument(name = "Employee repo: update_password", skip_all)]
+    pub async fn update_password(&self, email: &str, password: &str) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Employee repo: Updating password of {email}");
+        let _timer = telemetry::query_timer("employee", "update_password");
+        let mut tx = self.pool.begin().await?;
+        let employee_id: Option<i64> = sqlx::query_scalar(
+            "UPDATE employee SET password = $2, failed_logins = 0, locked_at = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE email = $1
+            RETURNING id",
+        )
+        .bind(email)
+        .bind(password)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        let Some(employee_id) = employee_id else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            "UPDATE session SET revoked_at = CURRENT_TIMESTAMP
+            WHERE employee_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(employee_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Деактивация также отзывает все сессии сотрудника.
+    /// Возвращает `false`, если сотрудника с таким email нет.
+    #[tracing::instrument(name = "Employee repo: set_active", skip_all)]
+    pub async fn set_active(&self, email: &str, active: bool) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Employee repo: Setting active = {active} for {email}");
+        let _timer = telemetry::query_timer("employee", "set_active");
+        sqlx::query_scalar::<_, i64>(
+            "WITH updated AS (
+                UPDATE employee SET active = $2, updated_at = CURRENT_TIMESTAMP
+                WHERE email = $1
+                RETURNING id
+            ), revoked AS (
+                UPDATE session SET revoked_at = CURRENT_TIMESTAMP
+                WHERE NOT $2 AND revoked_at IS NULL
+                    AND employee_id IN (SELECT id FROM updated)
+            )
+            SELECT count(*) FROM updated",
+        )
+        .bind(email)
+        .bind(active)
+        .fetch_one(&*self.pool)
+        .await
+        .map(|updated| updated > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Снимает блокировку после неудачных входов. Возвращает `false`, если сотрудника нет.
+    #[tracing::instrument(name = "Employee repo: unlock", skip_all)]
+    pub async fn unlock(&self, id: i64) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Employee repo: Unlocking employee {id}");
+        let _timer = telemetry::query_timer("employee", "unlock");
+        sqlx::query(
+            "UPDATE employee
+            SET failed_logins = 0, locked_at = NULL, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// То же, что `unlock`, по email. Возвращает `false`, если сотрудника нет.
+    #[tracing::instrument(name = "Employee repo: unlock_by_email", skip_all)]
+    pub async fn unlock_by_email(&self, email: &str) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Employee repo: Unlocking employee {email}");
+        let _timer = telemetry::query_timer("employee", "unlock_by_email");
+        sqlx::query(
+            "UPDATE employee
+            SET failed_logins = 0, locked_at = NULL, updated_at = CURRENT_TIMESTAMP
+            WHERE email = $1",
+        )
+        .bind(email)
+        .execute(&*self.pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
 }
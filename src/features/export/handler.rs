@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use tracing::Instrument;
+
+use super::logic::{ByteStream, Logic};
+use crate::features::auth::Principal;
+use crate::models::dao::Permission;
+use crate::models::dto::{Error, ExportFormat, ExportQuery, ReportFilter, ServiceFilter};
+
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn export_requests(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(query): Query<ExportQuery>,
+        Query(filter): Query<ReportFilter>,
+    ) -> Response {
+        let span = tracing::info_span!("Export handler: export_requests", format = ?query.format, filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                return err.into_response().into_response();
+            }
+            match query.format {
+                ExportFormat::Csv => match handler.logic.requests_csv(filter) {
+                    Ok(body) => file_response("requests", query.format, Body::from_stream(body)),
+                    Err(err) => error_response(err),
+                },
+                ExportFormat::Xlsx => match handler.logic.requests_xlsx(filter).await {
+                    Ok(body) => file_response("requests", query.format, Body::from(body)),
+                    Err(err) => error_response(err),
+                },
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn export_services(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(query): Query<ExportQuery>,
+        Query(filter): Query<ServiceFilter>,
+    ) -> Response {
+        let span = tracing::info_span!("Export handler: export_services", format = ?query.format, filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                return err.into_response().into_response();
+            }
+            match query.format {
+                ExportFormat::Csv => {
+                    let body: ByteStream = handler.logic.services_csv(filter);
+                    file_response("services", query.format, Body::from_stream(body))
+                }
+                ExportFormat::Xlsx => match handler.logic.services_xlsx(filter).await {
+                    Ok(body) => file_response("services", query.format, Body::from(body)),
+                    Err(err) => error_response(err),
+                },
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+fn file_response(name: &str, format: ExportFormat, body: Body) -> Response {
+    let (content_type, extension) = match format {
+        ExportFormat::Csv => (CSV_CONTENT_TYPE, "csv"),
+        ExportFormat::Xlsx => (XLSX_CONTENT_TYPE, "xlsx"),
+    };
+    let disposition = format!("attachment; filename=\"{}.{}\"", name, extension);
+
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    response
+}
+
+fn error_response(err: Error) -> Response {
+    tracing::error!("Failed to export: {:?}", err);
+    err.into_response().into_response()
+}
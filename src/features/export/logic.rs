@@ -0,0 +1,236 @@
+use std::{io, sync::Arc};
+
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, TryStreamExt, stream, stream::BoxStream};
+use rust_xlsxwriter::{Format, Workbook};
+
+use super::repo::Repo;
+use crate::models::dao::{self, RequestExport};
+use crate::models::dto::{Error, ReportFilter, ServiceFilter};
+
+pub type ByteStream = BoxStream<'static, Result<Bytes, io::Error>>;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+const REQUEST_COLUMNS: [&str; 10] = [
+    "ID",
+    "Название",
+    "Услуга",
+    "Автор",
+    "Исполнитель",
+    "Приоритет",
+    "Статус",
+    "Создана",
+    "Желаемый срок",
+    "Закрыта",
+];
+const SERVICE_COLUMNS: [&str; 10] = [
+    "ID",
+    "Название",
+    "Описание",
+    "Цена, мин. единиц валюты",
+    "Валюта",
+    "Длительность, мин",
+    "Уровень",
+    "Активна",
+    "Создана",
+    "Изменена",
+];
+
+/// Значение ячейки, общее для CSV и XLSX.
+enum Cell {
+    Int(i64),
+    Text(String),
+    Date(DateTime<Utc>),
+    Empty,
+}
+
+impl Cell {
+    fn to_csv(&self) -> String {
+        match self {
+            Cell::Int(v) => v.to_string(),
+            Cell::Text(v) => v.clone(),
+            Cell::Date(v) => v.format(DATETIME_FORMAT).to_string(),
+            Cell::Empty => String::new(),
+        }
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Cell::Empty)
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+
+impl From<DateTime<Utc>> for Cell {
+    fn from(value: DateTime<Utc>) -> Self {
+        Cell::Date(value)
+    }
+}
+
+impl From<i64> for Cell {
+    fn from(value: i64) -> Self {
+        Cell::Int(value)
+    }
+}
+
+impl From<bool> for Cell {
+    fn from(value: bool) -> Self {
+        Cell::Text(if value { "да" } else { "нет" }.to_string())
+    }
+}
+
+fn request_row(row: RequestExport) -> Vec<Cell> {
+    vec![
+        row.id.into(),
+        row.name.into(),
+        row.service_name.into(),
+        row.owner_name.into(),
+        row.assignee_name.into(),
+        i64::from(row.priority).into(),
+        i64::from(row.status).into(),
+        row.created_at.into(),
+        row.desired_at.into(),
+        row.closed_at.into(),
+    ]
+}
+
+fn service_row(row: dao::Service) -> Vec<Cell> {
+    let service = dao::Service::to_dto(row);
+    vec![
+        service.id.into(),
+        service.name.into(),
+        service.description.flatten().into(),
+        service.base_price.flatten().into(),
+        service.currency.flatten().into(),
+        service.duration_minutes.flatten().map(i64::from).into(),
+        service.skill_level.flatten().into(),
+        service.active.into(),
+        service.created_at.into(),
+        service.updated_at.into(),
+    ]
+}
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    pub fn requests_csv(&self, filter: ReportFilter) -> Result<ByteStream, Error> {
+        tracing::debug!("Export logic: Exporting requests to CSV");
+        filter.validate()?;
+        let rows = self.repo.stream_requests(filter).map_ok(request_row);
+        Ok(csv_stream(&REQUEST_COLUMNS, rows.boxed()))
+    }
+
+    #[tracing::instrument(name = "Export logic: requests_xlsx", skip_all)]
+    pub async fn requests_xlsx(&self, filter: ReportFilter) -> Result<Vec<u8>, Error> {
+        tracing::debug!("Export logic: Exporting requests to XLSX");
+        filter.validate()?;
+        let rows = self.repo.stream_requests(filter).map_ok(request_row);
+        xlsx("Заявки", &REQUEST_COLUMNS, rows.boxed()).await
+    }
+
+    pub fn services_csv(&self, filter: ServiceFilter) -> ByteStream {
+        tracing::debug!("Export logic: Exporting services to CSV");
+        let rows = self.repo.stream_services(filter).map_ok(service_row);
+        csv_stream(&SERVICE_COLUMNS, rows.boxed())
+    }
+
+    #[tracing::instrument(name = "Export logic: services_xlsx", skip_all)]
+    pub async fn services_xlsx(&self, filter: ServiceFilter) -> Result<Vec<u8>, Error> {
+        tracing::debug!("Export logic: Exporting services to XLSX");
+        let rows = self.repo.stream_services(filter).map_ok(service_row);
+        xlsx("Услуги", &SERVICE_COLUMNS, rows.boxed()).await
+    }
+}
+
+fn csv_line(fields: impl IntoIterator<Item = String>) -> Result<Bytes, io::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer
+        .into_inner()
+        .map(Bytes::from)
+        .map_err(|err| err.into_error())
+}
+
+/// CSV с BOM, чтобы Excel открывал UTF-8 без мастера импорта.
+fn csv_stream(
+    columns: &[&str],
+    rows: BoxStream<'static, Result<Vec<Cell>, sqlx::Error>>,
+) -> ByteStream {
+    let header = csv_line(columns.iter().map(|x| x.to_string())).map(|line| {
+        let mut chunk = UTF8_BOM.to_vec();
+        chunk.extend_from_slice(&line);
+        Bytes::from(chunk)
+    });
+
+    let body = rows
+        .map_err(io::Error::other)
+        .and_then(|cells| async move { csv_line(cells.iter().map(Cell::to_csv)) });
+
+    stream::once(async move { header }).chain(body).boxed()
+}
+
+/// XLSX нельзя отдавать по частям (это zip-архив), поэтому строки пишутся
+/// в лист с режимом constant memory, который сбрасывает их во временный файл.
+async fn xlsx(
+    sheet_name: &str,
+    columns: &[&str],
+    mut rows: BoxStream<'static, Result<Vec<Cell>, sqlx::Error>>,
+) -> Result<Vec<u8>, Error> {
+    let xlsx_error = |err: rust_xlsxwriter::XlsxError| {
+        tracing::error!("XLSX error: {err}");
+        Error::InternalServerError("Failed to build XLSX".to_string())
+    };
+
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+    let sheet = workbook.add_worksheet_with_constant_memory();
+    sheet.set_name(sheet_name).map_err(xlsx_error)?;
+    for (col, title) in columns.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, *title, &header_format)
+            .map_err(xlsx_error)?;
+    }
+
+    let mut row_num = 1;
+    while let Some(cells) = rows
+        .try_next()
+        .await
+        .map_err(|_| Error::InternalServerError("Internal database error".to_string()))?
+    {
+        for (col, cell) in cells.into_iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                Cell::Int(v) => sheet.write_number(row_num, col, v as f64),
+                Cell::Text(v) => sheet.write_string(row_num, col, v),
+                Cell::Date(v) => {
+                    sheet.write_datetime_with_format(row_num, col, v.naive_utc(), &date_format)
+                }
+                Cell::Empty => Ok(&mut *sheet),
+            }
+            .map_err(xlsx_error)?;
+        }
+        row_num += 1;
+    }
+
+    tokio::task::spawn_blocking(move || workbook.save_to_buffer())
+        .await
+        .map_err(|err| Error::InternalServerError(err.to_string()))?
+        .map_err(xlsx_error)
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::features::export::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+/// Фича только читает, поэтому ей можно передать пул реплики.
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/export/requests", get(Handler::export_requests))
+        .route("/export/services", get(Handler::export_services))
+        .with_state(handler)
+}
@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ErrorResponse, ExportFormat, ExportQuery, ReportFilter, ServiceFilter};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(export_requests, export_services),
+    components(schemas(ExportFormat, ErrorResponse)),
+    tags((name = "export", description = "Выгрузка в CSV и XLSX"))
+)]
+pub struct ApiDoc;
+
+/// Выгрузка заявок
+#[utoipa::path(
+    get,
+    path = "/export/requests",
+    tag = "export",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ExportQuery, ReportFilter),
+    responses(
+        (status = 200, description = "Файл выгрузки", content(
+            (String = "text/csv"),
+            (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 400, description = "Некорректные параметры", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn export_requests() {}
+
+/// Выгрузка каталога услуг
+#[utoipa::path(
+    get,
+    path = "/export/services",
+    tag = "export",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ExportQuery, ServiceFilter),
+    responses(
+        (status = 200, description = "Файл выгрузки", content(
+            (String = "text/csv"),
+            (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 400, description = "Неизвестный формат"),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn export_services() {}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use futures_util::{TryStreamExt, stream::BoxStream};
+use sqlx::PgPool;
+
+use crate::features::services::repo::FILTERED_SERVICES;
+use crate::models::dao::{RequestExport, Service};
+use crate::models::dto::{ReportFilter, ServiceFilter};
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+// Выгрузки читаются курсором: строки отдаются по мере получения из базы,
+// а не собираются в память целиком.
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    pub fn stream_requests(
+        &self,
+        filter: ReportFilter,
+    ) -> BoxStream<'static, Result<RequestExport, sqlx::Error>> {
+        tracing::debug!("Export repo: Streaming requests with {:?}", filter);
+        let pool = self.pool.clone();
+        Box::pin(async_stream::try_stream! {
+            let mut rows = sqlx::query_as::<_, RequestExport>(
+                "SELECT r.id, r.name,
+                    s.name AS service_name,
+                    concat_ws(' ', o.last_name, o.name, o.middle_name) AS owner_name,
+                    CASE WHEN e.id IS NULL THEN NULL
+                        ELSE concat_ws(' ', e.last_name, e.name, e.middle_name)
+                    END AS assignee_name,
+                    r.priority, r.status, r.created_at, r.desired_at, r.closed_at
+                FROM request r
+                LEFT JOIN service s ON s.id = r.service_id
+                JOIN employee o ON o.id = r.owner_id
+                LEFT JOIN employee e ON e.id = r.employee_id
+                WHERE ($1::date IS NULL OR r.created_at >= $1::date)
+                    AND ($2::date IS NULL OR r.created_at < $2::date + 1)
+                    AND ($3::bigint IS NULL OR r.service_id = $3)
+                ORDER BY r.id",
+            )
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(filter.service_id)
+            .fetch(&*pool);
+
+            while let Some(row) = rows.try_next().await.inspect_err(|err| {
+                tracing::error!("Database error: {err}");
+            })? {
+                yield row;
+            }
+        })
+    }
+
+    /// Услуги с тем же фильтром, что и у списка `/services`.
+    pub fn stream_services(
+        &self,
+        filter: ServiceFilter,
+    ) -> BoxStream<'static, Result<Service, sqlx::Error>> {
+        tracing::debug!("Export repo: Streaming services with {:?}", filter);
+        let pool = self.pool.clone();
+        Box::pin(async_stream::try_stream! {
+            let query = format!("{FILTERED_SERVICES} ORDER BY id");
+            let mut rows = sqlx::query_as::<_, Service>(&query)
+                .bind(filter.category_id)
+                .bind(filter.active)
+                .fetch(&*pool);
+
+            while let Some(row) = rows.try_next().await.inspect_err(|err| {
+                tracing::error!("Database error: {err}");
+            })? {
+                yield row;
+            }
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::models::dto::HealthStatus;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn live(State(handler): State<Arc<Handler>>) -> (StatusCode, Json<Value>) {
+        (StatusCode::OK, Json(json!(handler.logic.live())))
+    }
+
+    pub async fn ready(State(handler): State<Arc<Handler>>) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Health handler: ready");
+        async {
+            let result = handler.logic.ready().await;
+            if result.status == HealthStatus::Up {
+                (StatusCode::OK, Json(json!(result)))
+            } else {
+                tracing::warn!("Service is not ready: {:?}", result);
+                (StatusCode::SERVICE_UNAVAILABLE, Json(json!(result)))
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::repo::Repo;
+use crate::db::MIGRATOR;
+use crate::models::dto::{
+    DatabaseCheck, HealthStatus, Liveness, MigrationsCheck, PoolCheck, Readiness,
+};
+
+// Пул создаётся через `connect_lazy`, и без своего таймаута проверка висела бы
+// до `acquire_timeout` пула, пока оркестратор уже считает её упавшей.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    pub fn live(&self) -> Liveness {
+        Liveness {
+            status: HealthStatus::Up,
+        }
+    }
+
+    #[tracing::instrument(name = "Health logic: ready", skip_all)]
+    pub async fn ready(&self) -> Readiness {
+        tracing::debug!("Health logic: Checking readiness");
+        // Снимок до проверок, иначе в нём были бы и их собственные соединения
+        let stats = self.repo.pool_stats();
+        let database = self.check_database().await;
+        let migrations = self.check_migrations().await;
+        let pool = check_pool(stats, database.status);
+
+        let status = if [database.status, migrations.status, pool.status]
+            .iter()
+            .all(|s| *s == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+
+        Readiness {
+            status,
+            database,
+            migrations,
+            pool,
+        }
+    }
+
+    async fn check_database(&self) -> DatabaseCheck {
+        let started = Instant::now();
+        match tokio::time::timeout(CHECK_TIMEOUT, self.repo.ping()).await {
+            Ok(Ok(())) => DatabaseCheck {
+                status: HealthStatus::Up,
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                error: None,
+            },
+            Ok(Err(err)) => DatabaseCheck {
+                status: HealthStatus::Down,
+                latency_ms: None,
+                error: Some(err.to_string()),
+            },
+            Err(_) => DatabaseCheck {
+                status: HealthStatus::Down,
+                latency_ms: None,
+                error: Some("Database ping timed out".to_string()),
+            },
+        }
+    }
+
+    async fn check_migrations(&self) -> MigrationsCheck {
+        let applied =
+            match tokio::time::timeout(CHECK_TIMEOUT, self.repo.applied_migrations()).await {
+                Ok(Ok(applied)) => applied,
+                Ok(Err(err)) => {
+                    return MigrationsCheck {
+                        status: HealthStatus::Down,
+                        pending: Vec::new(),
+                        error: Some(err.to_string()),
+                    };
+                }
+                Err(_) => {
+                    return MigrationsCheck {
+                        status: HealthStatus::Down,
+                        pending: Vec::new(),
+                        error: Some("Migrations check timed out".to_string()),
+                    };
+                }
+            };
+
+        let pending: Vec<i64> = MIGRATOR
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+            .map(|m| m.version)
+            .collect();
+
+        MigrationsCheck {
+            status: if pending.is_empty() {
+                HealthStatus::Up
+            } else {
+                HealthStatus::Down
+            },
+            pending,
+            error: None,
+        }
+    }
+}
+
+/// Занятость пула. Полностью занятый пул ещё обслуживает запросы, пусть и с очередью,
+/// поэтому сама по себе она только показывается: `Down` ставится, если соединение не
+/// удалось получить или пинг не прошёл (`database`).
+fn check_pool(
+    (size, idle, max_connections): (u32, usize, u32),
+    database: HealthStatus,
+) -> PoolCheck {
+    let in_use = (size as usize).saturating_sub(idle);
+    let saturation = if max_connections == 0 {
+        1.0
+    } else {
+        in_use as f64 / max_connections as f64
+    };
+
+    PoolCheck {
+        status: database,
+        size,
+        idle,
+        max_connections,
+        saturation,
+    }
+}
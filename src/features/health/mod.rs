@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::features::health::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/health/live", get(Handler::live))
+        .route("/health/ready", get(Handler::ready))
+        .with_state(handler)
+}
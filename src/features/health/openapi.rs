@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{DatabaseCheck, Liveness, MigrationsCheck, PoolCheck, Readiness};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(live, ready),
+    components(schemas(Liveness, Readiness, DatabaseCheck, MigrationsCheck, PoolCheck)),
+    tags((name = "health", description = "Проверки для оркестратора"))
+)]
+pub struct ApiDoc;
+
+/// Процесс жив и обрабатывает запросы
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses((status = 200, description = "Процесс работает", body = Liveness))
+)]
+fn live() {}
+
+/// Готовность принимать трафик
+///
+/// Проверяет доступность базы, применённость встроенных миграций и заполненность пула.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Все проверки прошли", body = Readiness),
+        (status = 503, description = "Хотя бы одна проверка не прошла", body = Readiness)
+    )
+)]
+fn ready() {}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::telemetry;
+use sqlx::PgPool;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "Health repo: ping", skip_all)]
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        tracing::debug!("Health repo: Pinging database");
+        let _timer = telemetry::query_timer("health", "ping");
+        sqlx::query("SELECT 1")
+            .execute(&*self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Версии успешно применённых миграций из служебной таблицы sqlx.
+    #[tracing::instrument(name = "Health repo: applied_migrations", skip_all)]
+    pub async fn applied_migrations(&self) -> Result<Vec<i64>, sqlx::Error> {
+        tracing::debug!("Health repo: Getting applied migrations");
+        let _timer = telemetry::query_timer("health", "applied_migrations");
+        sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Текущий размер пула, число простаивающих соединений и верхний предел.
+    pub fn pool_stats(&self) -> (u32, usize, u32) {
+        (
+            self.pool.size(),
+            self.pool.num_idle(),
+            self.pool.options().get_max_connections(),
+        )
+    }
+}
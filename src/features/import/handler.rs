@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::features::auth::AuthEmployee;
+use crate::models::dao::Role;
+use crate::models::dto::{Error, ImportQuery, ImportReport};
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn import_services(
+        State(handler): State<Arc<Handler>>,
+        Query(query): Query<ImportQuery>,
+        body: String,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Import handler: import_services", dry_run = query.dry_run);
+        async { report_response(handler.logic.import_services(&body, query.dry_run).await) }
+            .instrument(span)
+            .await
+    }
+
+    /// Импорт сотрудников, только для суперадмина.
+    pub async fn import_employees(
+        auth: AuthEmployee,
+        State(handler): State<Arc<Handler>>,
+        Query(query): Query<ImportQuery>,
+        body: String,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!(
+            "Import handler: import_employees",
+            dry_run = query.dry_run,
+            by = auth.id
+        );
+        async {
+            let result = match auth.require(&[Role::Superadmin]) {
+                Ok(()) => handler.logic.import_employees(&body, query.dry_run).await,
+                Err(err) => Err(err),
+            };
+            report_response(result)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+fn report_response(result: Result<ImportReport, Error>) -> (StatusCode, Json<Value>) {
+    match result {
+        Ok(report) if !report.errors.is_empty() => {
+            tracing::warn!("Import rejected: {} invalid rows", report.errors.len());
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(json!(report)))
+        }
+        Ok(report) if report.dry_run => (StatusCode::OK, Json(json!(report))),
+        Ok(report) => {
+            tracing::debug!("Imported {} rows", report.imported);
+            (StatusCode::CREATED, Json(json!(report)))
+        }
+        Err(err) => {
+            tracing::error!("Failed to import: {:?}", err);
+            let (status, Json(error_response)) = err.into_response();
+            (status, Json(json!(error_response)))
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use futures_util::{StreamExt, TryStreamExt, stream};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use sqlx::{Connection, Postgres, Transaction};
+
+use super::repo::Repo;
+use crate::features::{employee, services, webhooks::Dispatcher};
+use crate::models::dao::{self, WebhookEvent};
+use crate::models::dto::{Employee, Error, ImportReport, ImportRowError, Service};
+use crate::{i18n, password};
+
+pub struct Logic {
+    repo: Arc<Repo>,
+    dispatcher: Dispatcher,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>, dispatcher: Dispatcher) -> Self {
+        Logic { repo, dispatcher }
+    }
+
+    #[tracing::instrument(name = "Import logic: import_services", skip_all)]
+    pub async fn import_services(&self, csv: &str, dry_run: bool) -> Result<ImportReport, Error> {
+        tracing::debug!("Import logic: Importing services, dry_run = {}", dry_run);
+        let mut tx = self.repo.begin().await.map_err(database_error)?;
+        let mut report = ImportReport::new(dry_run);
+        let mut created = Vec::<Service>::new();
+
+        for (row, record) in parse::<Service>(csv) {
+            report.total += 1;
+            let result = match record {
+                Ok(payload) => Self::import_service(&mut tx, payload).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(service) => created.push(service),
+                // Сбой базы - не ошибка строки, импорт прерывается, транзакция откатывается
+                Err(err @ Error::InternalServerError(_)) => return Err(err),
+                Err(err) => report.errors.push(row_error(row, err)),
+            }
+        }
+
+        report.valid = created.len();
+        Self::finish(tx, &mut report).await?;
+        if report.imported > 0 {
+            for service in created {
+                self.dispatcher
+                    .dispatch(WebhookEvent::ServiceCreated, json!(service));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Импорт сотрудников: проверка строк, хеширование паролей параллельно (не больше
+    /// `password::concurrency` одновременно) и запись в одной транзакции.
+    #[tracing::instrument(name = "Import logic: import_employees", skip_all)]
+    pub async fn import_employees(&self, csv: &str, dry_run: bool) -> Result<ImportReport, Error> {
+        tracing::debug!("Import logic: Importing employees, dry_run = {}", dry_run);
+        let mut report = ImportReport::new(dry_run);
+        let mut rows = Vec::new();
+        for (row, record) in parse::<Employee>(csv) {
+            report.total += 1;
+            let result = record.and_then(|payload| {
+                let role = employee::logic::Logic::validate(&payload)?;
+                Ok((payload, role))
+            });
+            match result {
+                Ok((payload, role)) => rows.push((row, payload, role)),
+                Err(err) => report.errors.push(row_error(row, err)),
+            }
+        }
+
+        // Хеши нужны только для записи: при проверке или ошибках в файле транзакция
+        // всё равно откатывается, поэтому дорогой bcrypt не нужен
+        let passwords: Vec<String> = if dry_run || !report.errors.is_empty() {
+            vec![String::new(); rows.len()]
+        } else {
+            let plain: Vec<String> = rows
+                .iter()
+                .map(|(_, payload, _)| payload.password.clone().unwrap_or_default())
+                .collect();
+            stream::iter(plain)
+                .map(|plain| async move { password::hash(&plain).await })
+                .buffered(password::concurrency())
+                .try_collect()
+                .await?
+        };
+
+        let mut tx = self.repo.begin().await.map_err(database_error)?;
+        for ((row, payload, role), password) in rows.into_iter().zip(passwords) {
+            match Self::import_employee(&mut tx, payload, role, password).await {
+                Ok(()) => report.valid += 1,
+                // Сбой базы - не ошибка строки, импорт прерывается, транзакция откатывается
+                Err(err @ Error::InternalServerError(_)) => return Err(err),
+                Err(err) => report.errors.push(row_error(row, err)),
+            }
+        }
+        report.errors.sort_by_key(|error| error.row);
+
+        Self::finish(tx, &mut report).await?;
+        Ok(report)
+    }
+
+    async fn import_service(
+        tx: &mut Transaction<'static, Postgres>,
+        payload: Service,
+    ) -> Result<Service, Error> {
+        services::logic::Logic::validate(&payload)?;
+
+        // Точка сохранения, чтобы ошибка в строке не обрывала всю транзакцию
+        let mut savepoint = tx.begin().await.map_err(database_error)?;
+        let service = services::repo::Repo::insert(&mut *savepoint, &payload)
+            .await
+            .map(dao::Service::to_dto)
+            .map_err(|err| services::logic::Logic::write_error(err, &payload))?;
+        savepoint.commit().await.map_err(database_error)?;
+
+        Ok(service)
+    }
+
+    async fn import_employee(
+        tx: &mut Transaction<'static, Postgres>,
+        payload: Employee,
+        role: dao::Role,
+        password: String,
+    ) -> Result<(), Error> {
+        let mut savepoint = tx.begin().await.map_err(database_error)?;
+        employee::repo::Repo::insert(
+            &mut *savepoint,
+            payload.name.unwrap(),
+            payload.last_name.unwrap(),
+            payload.middle_name,
+            payload.email.unwrap(),
+            password,
+            role,
+        )
+        .await
+        .map_err(|err| {
+            if err
+                .as_database_error()
+                .is_some_and(|err| err.is_unique_violation())
+            {
+                Error::Conflict(String::from("Employee already is exists"))
+            } else {
+                database_error(err)
+            }
+        })?;
+        savepoint.commit().await.map_err(database_error)?;
+
+        Ok(())
+    }
+
+    /// Фиксирует транзакцию, только если все строки корректны и это не проверка.
+    async fn finish(
+        tx: Transaction<'static, Postgres>,
+        report: &mut ImportReport,
+    ) -> Result<(), Error> {
+        if report.errors.is_empty() && !report.dry_run {
+            tx.commit().await.map_err(database_error)?;
+            report.imported = report.valid;
+        } else {
+            tx.rollback().await.map_err(database_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl ImportReport {
+    fn new(dry_run: bool) -> Self {
+        ImportReport {
+            dry_run,
+            total: 0,
+            valid: 0,
+            imported: 0,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Разбирает CSV с заголовком; для каждой записи возвращает номер строки в файле.
+fn parse<T: DeserializeOwned>(csv: &str) -> Vec<(usize, Result<T, Error>)> {
+    let csv = csv.strip_prefix('\u{feff}').unwrap_or(csv);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(err) => return vec![(1, Err(Error::BadRequest(err.to_string())))],
+    };
+
+    reader
+        .into_records()
+        .enumerate()
+        .map(|(index, record)| match record {
+            Ok(record) => {
+                let row = record.position().map_or(index + 2, |x| x.line() as usize);
+                let payload = record
+                    .deserialize::<T>(Some(&headers))
+                    .map_err(|err| Error::BadRequest(err.to_string()));
+                (row, payload)
+            }
+            Err(err) => {
+                let row = err.position().map_or(index + 2, |x| x.line() as usize);
+                (row, Err(Error::BadRequest(err.to_string())))
+            }
+        })
+        .collect()
+}
+
+fn row_error(row: usize, err: Error) -> ImportRowError {
+    ImportRowError {
+        row,
+        error: i18n::translate(err.message()),
+    }
+}
+
+fn database_error(err: sqlx::Error) -> Error {
+    tracing::error!("Database error: {err}");
+    Error::InternalServerError("Internal database error".to_string())
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ErrorResponse, ImportQuery, ImportReport, ImportRowError};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(import_services, import_employees),
+    components(schemas(ImportReport, ImportRowError, ErrorResponse)),
+    tags((name = "import", description = "Импорт из CSV"))
+)]
+pub struct ApiDoc;
+
+/// Импорт услуг
+///
+/// CSV с заголовком и колонкой `name`.
+#[utoipa::path(
+    post,
+    path = "/import/services",
+    tag = "import",
+    params(ImportQuery),
+    request_body(content = String, content_type = "text/csv"),
+    responses(
+        (status = 200, description = "Проверка без записи прошла", body = ImportReport),
+        (status = 201, description = "Все строки импортированы", body = ImportReport),
+        (status = 422, description = "Есть ошибки, ничего не записано", body = ImportReport),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn import_services() {}
+
+/// Импорт сотрудников
+///
+/// CSV с заголовком и колонками `name`, `last_name`, `middle_name`, `email`, `password`, `role`.
+/// Роль - код из `/employee/roles`. Доступно только суперадмину.
+#[utoipa::path(
+    post,
+    path = "/import/employees",
+    tag = "import",
+    params(ImportQuery),
+    request_body(content = String, content_type = "text/csv"),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Проверка без записи прошла", body = ImportReport),
+        (status = 201, description = "Все строки импортированы", body = ImportReport),
+        (status = 401, description = "Нет или недействителен access токен", body = ErrorResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorResponse),
+        (status = 422, description = "Есть ошибки, ничего не записано", body = ImportReport),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn import_employees() {}
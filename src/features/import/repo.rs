@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::telemetry;
+use sqlx::{PgPool, Postgres, Transaction};
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    /// Импорт целиком выполняется в одной транзакции; вставки идут через
+    /// `Repo::insert` соответствующих фич.
+    #[tracing::instrument(name = "Import repo: begin", skip_all)]
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        tracing::debug!("Import repo: Starting transaction");
+        let _timer = telemetry::query_timer("import", "begin");
+        self.pool.begin().await.map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
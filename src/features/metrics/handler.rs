@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+
+use super::logic::Logic;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn render(
+        State(handler): State<Arc<Handler>>,
+    ) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            handler.logic.render().await,
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use super::repo::Repo;
+use crate::telemetry;
+
+pub struct Logic {
+    repo: Arc<Repo>,
+    // Сочетания статуса и приоритета, уже попавшие в метрики. Когда открытых заявок
+    // с таким сочетанием не остаётся, значение нужно обнулить, а не оставить старым.
+    seen_open: Mutex<HashSet<(i16, i16)>>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic {
+            repo,
+            seen_open: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Обновляет метрики, снимаемые в момент запроса, и отдаёт все метрики в формате Prometheus.
+    #[tracing::instrument(name = "Metrics logic: render", skip_all)]
+    pub async fn render(&self) -> String {
+        tracing::debug!("Metrics logic: Rendering metrics");
+        let mut in_use_total = 0;
+        for pool in self.repo.pool_stats() {
+            let in_use = (pool.size as usize).saturating_sub(pool.idle);
+            in_use_total += in_use;
+            metrics::gauge!("db_pool_connections", "pool" => pool.name, "state" => "idle")
+                .set(pool.idle as f64);
+            metrics::gauge!("db_pool_connections", "pool" => pool.name, "state" => "in_use")
+                .set(in_use as f64);
+            metrics::gauge!("db_pool_size", "pool" => pool.name).set(pool.size as f64);
+            metrics::gauge!("db_pool_max_connections", "pool" => pool.name)
+                .set(pool.max_connections as f64);
+        }
+        // Оценка снизу и без разбивки по пулам: очередь пула sqlx не показывает, а
+        // соединения, взятые в обход репозиториев (транзакции импорта, проверки
+        // готовности), считаются занятыми без выполняющегося запроса
+        metrics::gauge!("db_pool_waiters")
+            .set(telemetry::queries_in_flight().saturating_sub(in_use_total) as f64);
+
+        // Ошибка базы не должна ронять остальные метрики: ряды просто не обновятся
+        if let Ok(rows) = self.repo.open_requests().await {
+            let mut seen = self.seen_open.lock().unwrap();
+            let current: HashSet<(i16, i16)> =
+                rows.iter().map(|r| (r.status, r.priority)).collect();
+            for (status, priority) in seen.difference(&current) {
+                open_requests_gauge(*status, *priority).set(0.0);
+            }
+            for row in &rows {
+                open_requests_gauge(row.status, row.priority).set(row.count as f64);
+            }
+            *seen = seen.union(&current).copied().collect();
+        }
+
+        telemetry::prometheus().render()
+    }
+}
+
+fn open_requests_gauge(status: i16, priority: i16) -> metrics::Gauge {
+    metrics::gauge!(
+        "requests_open",
+        "status" => status.to_string(),
+        "priority" => priority.to_string(),
+    )
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::features::metrics::{handler::Handler, logic::Logic, repo::Repo};
+use crate::telemetry;
+
+pub mod handler;
+pub mod logic;
+pub mod repo;
+
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    with_read_pool(pool, None)
+}
+
+/// Как `new`, но бизнес-метрики считаются на реплике, а её пул тоже попадает в метрики.
+pub fn with_read_pool(pool: &sqlx::PgPool, read_pool: Option<&sqlx::PgPool>) -> Router {
+    // Рекордер нужен до первого запроса, иначе метрики уйдут в пустой рекордер по умолчанию
+    telemetry::prometheus();
+
+    let repo = Arc::new(Repo::new(
+        Arc::new(pool.clone()),
+        read_pool.map(|p| Arc::new(p.clone())),
+    ));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/metrics", get(Handler::render))
+        .with_state(handler)
+}
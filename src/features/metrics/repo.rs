@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::models::dao::OpenRequests;
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+    read_pool: Option<Arc<PgPool>>,
+}
+
+/// Состояние пула на момент сбора метрик.
+pub struct PoolStats {
+    pub name: &'static str,
+    pub size: u32,
+    pub idle: usize,
+    pub max_connections: u32,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>, read_pool: Option<Arc<PgPool>>) -> Self {
+        Repo { pool, read_pool }
+    }
+
+    #[tracing::instrument(name = "Metrics repo: open_requests", skip_all)]
+    pub async fn open_requests(&self) -> Result<Vec<OpenRequests>, sqlx::Error> {
+        tracing::debug!("Metrics repo: Counting open requests");
+        let _timer = telemetry::query_timer("metrics", "open_requests");
+        sqlx::query_as::<_, OpenRequests>(
+            "SELECT status, priority, COUNT(*) AS count
+            FROM request
+            WHERE closed_at IS NULL
+            GROUP BY status, priority",
+        )
+        .fetch_all(&**self.read_pool.as_ref().unwrap_or(&self.pool))
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    pub fn pool_stats(&self) -> Vec<PoolStats> {
+        let mut pools = vec![("primary", &self.pool)];
+        if let Some(read_pool) = &self.read_pool {
+            pools.push(("replica", read_pool));
+        }
+
+        pools
+            .into_iter()
+            .map(|(name, pool)| PoolStats {
+                name,
+                size: pool.size(),
+                idle: pool.num_idle(),
+                max_connections: pool.options().get_max_connections(),
+            })
+            .collect()
+    }
+}
@@ -1,2 +1,12 @@
+pub mod api_keys;
+pub mod auth;
+pub mod categories;
 pub mod employee;
+pub mod export;
+pub mod health;
+pub mod import;
+pub mod metrics;
+pub mod reports;
+pub mod requests;
 pub mod services;
+pub mod webhooks;
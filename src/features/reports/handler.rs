@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::features::auth::Principal;
+use crate::models::dao::Permission;
+use crate::models::dto::ReportFilter;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn daily_volume(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(filter): Query<ReportFilter>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Report handler: daily_volume", filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.daily_volume(filter).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to build daily volume report: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn resolution_time(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(filter): Query<ReportFilter>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Report handler: resolution_time", filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.resolution_time(filter).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to build resolution time report: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn employee_workload(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(filter): Query<ReportFilter>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Report handler: employee_workload", filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.employee_workload(filter).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to build workload report: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn sla_compliance(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Query(filter): Query<ReportFilter>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Report handler: sla_compliance", filter = ?filter);
+        async {
+            if let Err(err) = principal.require(Permission::ReportsRead) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.sla_compliance(filter).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to build SLA report: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
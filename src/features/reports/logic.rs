@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use super::repo::Repo;
+use crate::models::dao;
+use crate::models::dto::{
+    DailyVolume, EmployeeWorkload, Error, ReportFilter, ResolutionTime, SlaCompliance,
+};
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    #[tracing::instrument(name = "Report logic: daily_volume", skip_all)]
+    pub async fn daily_volume(&self, filter: ReportFilter) -> Result<Vec<DailyVolume>, Error> {
+        tracing::debug!("Report logic: Getting daily volume");
+        filter.validate()?;
+        self.repo
+            .daily_volume(&filter)
+            .await
+            .map(|v| v.into_iter().map(dao::DailyVolume::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "Report logic: resolution_time", skip_all)]
+    pub async fn resolution_time(
+        &self,
+        filter: ReportFilter,
+    ) -> Result<Vec<ResolutionTime>, Error> {
+        tracing::debug!("Report logic: Getting resolution time");
+        filter.validate()?;
+        self.repo
+            .resolution_time(&filter)
+            .await
+            .map(|v| v.into_iter().map(dao::ResolutionTime::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "Report logic: employee_workload", skip_all)]
+    pub async fn employee_workload(
+        &self,
+        filter: ReportFilter,
+    ) -> Result<Vec<EmployeeWorkload>, Error> {
+        tracing::debug!("Report logic: Getting employee workload");
+        filter.validate()?;
+        self.repo
+            .employee_workload(&filter)
+            .await
+            .map(|v| v.into_iter().map(dao::EmployeeWorkload::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "Report logic: sla_compliance", skip_all)]
+    pub async fn sla_compliance(&self, filter: ReportFilter) -> Result<SlaCompliance, Error> {
+        tracing::debug!("Report logic: Getting SLA compliance");
+        filter.validate()?;
+        self.repo
+            .sla_compliance(&filter)
+            .await
+            .map(dao::SlaCompliance::to_dto)
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+}
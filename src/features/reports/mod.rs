@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::features::reports::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+/// Фича только читает, поэтому ей можно передать пул реплики.
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/reports/requests/daily", get(Handler::daily_volume))
+        .route("/reports/resolution-time", get(Handler::resolution_time))
+        .route("/reports/workload", get(Handler::employee_workload))
+        .route("/reports/sla", get(Handler::sla_compliance))
+        .with_state(handler)
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{
+    DailyVolume, EmployeeWorkload, ErrorResponse, ReportFilter, ResolutionTime, SlaCompliance,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(daily_volume, resolution_time, employee_workload, sla_compliance),
+    components(schemas(
+        DailyVolume,
+        ResolutionTime,
+        EmployeeWorkload,
+        SlaCompliance,
+        ErrorResponse
+    )),
+    tags((name = "reports", description = "Отчёты по заявкам"))
+)]
+pub struct ApiDoc;
+
+/// Открытые и закрытые заявки по дням
+#[utoipa::path(
+    get,
+    path = "/reports/requests/daily",
+    tag = "reports",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ReportFilter),
+    responses(
+        (status = 200, description = "Количество по дням", body = Vec<DailyVolume>),
+        (status = 400, description = "Некорректный диапазон дат", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn daily_volume() {}
+
+/// Медиана и p90 времени закрытия по услугам
+#[utoipa::path(
+    get,
+    path = "/reports/resolution-time",
+    tag = "reports",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ReportFilter),
+    responses(
+        (status = 200, description = "Время закрытия в секундах", body = Vec<ResolutionTime>),
+        (status = 400, description = "Некорректный диапазон дат", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn resolution_time() {}
+
+/// Открытые заявки по активным сотрудникам
+#[utoipa::path(
+    get,
+    path = "/reports/workload",
+    tag = "reports",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ReportFilter),
+    responses(
+        (status = 200, description = "Нагрузка сотрудников", body = Vec<EmployeeWorkload>),
+        (status = 400, description = "Некорректный диапазон дат", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn employee_workload() {}
+
+/// Соблюдение желаемого срока `desired_at`
+#[utoipa::path(
+    get,
+    path = "/reports/sla",
+    tag = "reports",
+    security(("bearer" = []), ("api_key" = [])),
+    params(ReportFilter),
+    responses(
+        (status = 200, description = "Соблюдение сроков", body = SlaCompliance),
+        (status = 400, description = "Некорректный диапазон дат", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права reports:read", body = ErrorResponse)
+    )
+)]
+fn sla_compliance() {}
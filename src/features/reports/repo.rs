@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::models::dao::{DailyVolume, EmployeeWorkload, ResolutionTime, SlaCompliance};
+use crate::models::dto::ReportFilter;
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+// Все отчёты принимают одинаковые параметры:
+// $1 - начало диапазона (date, включительно), $2 - конец диапазона (date, включительно), $3 - id услуги.
+// NULL в любом из параметров отключает соответствующий фильтр.
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "Report repo: daily_volume", skip_all)]
+    pub async fn daily_volume(
+        &self,
+        filter: &ReportFilter,
+    ) -> Result<Vec<DailyVolume>, sqlx::Error> {
+        tracing::debug!("Report repo: Getting daily volume with {:?}", filter);
+        let _timer = telemetry::query_timer("reports", "daily_volume");
+        sqlx::query_as::<_, DailyVolume>(
+            "WITH opened AS (
+                SELECT created_at::date AS day, COUNT(*) AS opened
+                FROM request
+                WHERE ($1::date IS NULL OR created_at >= $1::date)
+                    AND ($2::date IS NULL OR created_at < $2::date + 1)
+                    AND ($3::bigint IS NULL OR service_id = $3)
+                GROUP BY 1
+            ), closed AS (
+                SELECT closed_at::date AS day, COUNT(*) AS closed
+                FROM request
+                WHERE closed_at IS NOT NULL
+                    AND ($1::date IS NULL OR closed_at >= $1::date)
+                    AND ($2::date IS NULL OR closed_at < $2::date + 1)
+                    AND ($3::bigint IS NULL OR service_id = $3)
+                GROUP BY 1
+            )
+            SELECT COALESCE(o.day, c.day) AS day,
+                COALESCE(o.opened, 0) AS opened,
+                COALESCE(c.closed, 0) AS closed
+            FROM opened o
+            FULL OUTER JOIN closed c ON o.day = c.day
+            ORDER BY day",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.service_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Report repo: resolution_time", skip_all)]
+    pub async fn resolution_time(
+        &self,
+        filter: &ReportFilter,
+    ) -> Result<Vec<ResolutionTime>, sqlx::Error> {
+        tracing::debug!("Report repo: Getting resolution time with {:?}", filter);
+        let _timer = telemetry::query_timer("reports", "resolution_time");
+        sqlx::query_as::<_, ResolutionTime>(
+            "SELECT r.service_id::bigint AS service_id,
+                s.name AS service_name,
+                COUNT(*) AS closed,
+                percentile_cont(0.5) WITHIN GROUP (
+                    ORDER BY EXTRACT(EPOCH FROM r.closed_at - r.created_at)::double precision
+                ) AS median_seconds,
+                percentile_cont(0.9) WITHIN GROUP (
+                    ORDER BY EXTRACT(EPOCH FROM r.closed_at - r.created_at)::double precision
+                ) AS p90_seconds
+            FROM request r
+            LEFT JOIN service s ON s.id = r.service_id
+            WHERE r.closed_at IS NOT NULL
+                AND ($1::date IS NULL OR r.closed_at >= $1::date)
+                AND ($2::date IS NULL OR r.closed_at < $2::date + 1)
+                AND ($3::bigint IS NULL OR r.service_id = $3)
+            GROUP BY r.service_id, s.name
+            ORDER BY r.service_id NULLS LAST",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.service_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Report repo: employee_workload", skip_all)]
+    pub async fn employee_workload(
+        &self,
+        filter: &ReportFilter,
+    ) -> Result<Vec<EmployeeWorkload>, sqlx::Error> {
+        tracing::debug!("Report repo: Getting employee workload with {:?}", filter);
+        let _timer = telemetry::query_timer("reports", "employee_workload");
+        sqlx::query_as::<_, EmployeeWorkload>(
+            "SELECT e.id AS employee_id, e.name, e.last_name, COUNT(r.id) AS open_requests
+            FROM employee e
+            LEFT JOIN request r ON r.employee_id = e.id
+                AND r.closed_at IS NULL
+                AND ($1::date IS NULL OR r.created_at >= $1::date)
+                AND ($2::date IS NULL OR r.created_at < $2::date + 1)
+                AND ($3::bigint IS NULL OR r.service_id = $3)
+            WHERE e.active
+            GROUP BY e.id
+            ORDER BY open_requests DESC, e.id",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.service_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    /// Соблюдение срока `desired_at` по заявкам, срок которых попадает в диапазон.
+    #[tracing::instrument(name = "Report repo: sla_compliance", skip_all)]
+    pub async fn sla_compliance(
+        &self,
+        filter: &ReportFilter,
+    ) -> Result<SlaCompliance, sqlx::Error> {
+        tracing::debug!("Report repo: Getting SLA compliance with {:?}", filter);
+        let _timer = telemetry::query_timer("reports", "sla_compliance");
+        sqlx::query_as::<_, SlaCompliance>(
+            "SELECT
+                COUNT(*) FILTER (WHERE closed_at IS NOT NULL AND closed_at <= desired_at) AS met,
+                COUNT(*) FILTER (
+                    WHERE (closed_at IS NOT NULL AND closed_at > desired_at)
+                        OR (closed_at IS NULL AND desired_at < NOW())
+                ) AS breached,
+                COUNT(*) FILTER (WHERE closed_at IS NULL AND desired_at >= NOW()) AS pending
+            FROM request
+            WHERE ($1::date IS NULL OR desired_at >= $1::date)
+                AND ($2::date IS NULL OR desired_at < $2::date + 1)
+                AND ($3::bigint IS NULL OR service_id = $3)",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.service_id)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
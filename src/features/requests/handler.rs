@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::features::auth::Principal;
+use crate::models::dao::Permission;
+use crate::models::dto::Request;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn create_request(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<Request>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Request handler: create_request", name = ?payload.name);
+        async {
+            let result = match principal.require(Permission::RequestsCreate) {
+                Ok(()) => handler.logic.create(&principal, payload).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(result) => {
+                    tracing::debug!("Request created successfully: {:?}", result.id);
+                    (StatusCode::CREATED, Json(json!(result)))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to create request: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
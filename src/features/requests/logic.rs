@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::repo::Repo;
+use crate::features::auth::Principal;
+use crate::features::webhooks::Dispatcher;
+use crate::models::dao::{self, WebhookEvent};
+use crate::models::dto::{Error, Request};
+
+pub struct Logic {
+    repo: Arc<Repo>,
+    dispatcher: Dispatcher,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>, dispatcher: Dispatcher) -> Self {
+        Logic { repo, dispatcher }
+    }
+
+    /// Создаёт заявку от имени `principal`. Право `requests:create` проверяет обработчик.
+    #[tracing::instrument(name = "Request logic: create", skip_all)]
+    pub async fn create(&self, principal: &Principal, payload: Request) -> Result<Request, Error> {
+        tracing::debug!("Request logic: Creating request");
+        Self::validate(&payload)?;
+
+        let owner_id = match principal {
+            Principal::Employee(employee) => employee.id,
+            Principal::ApiKey { id, .. } => self
+                .repo
+                .api_key_owner(*id)
+                .await
+                .map_err(database_error)?
+                .ok_or_else(|| Error::Forbidden("API key has no owner employee".to_string()))?,
+        };
+
+        if let Some(service_id) = payload.service_id {
+            match self.repo.service_active(service_id).await {
+                Ok(Some(true)) => {}
+                Ok(Some(false)) => {
+                    return Err(Error::BadRequest(format!(
+                        "Service with id: {} is not active",
+                        service_id
+                    )));
+                }
+                Ok(None) => {
+                    return Err(Error::BadRequest(format!(
+                        "Service with id: {} not found",
+                        service_id
+                    )));
+                }
+                Err(err) => return Err(database_error(err)),
+            }
+        }
+
+        let request = self
+            .repo
+            .insert(&payload, owner_id)
+            .await
+            .map(dao::Request::to_dto)
+            .map_err(database_error)?;
+
+        self.dispatcher
+            .dispatch(WebhookEvent::RequestCreated, json!(request));
+        Ok(request)
+    }
+
+    fn validate(payload: &Request) -> Result<(), Error> {
+        if payload.name.trim().is_empty() {
+            return Err(Error::BadRequest(
+                "Field 'name' can't be empty.".to_string(),
+            ));
+        }
+        if payload.desired_at.is_none() {
+            return Err(Error::BadRequest(
+                "Field 'desired_at' is required.".to_string(),
+            ));
+        }
+        if payload.priority.is_some_and(|priority| priority < 0) {
+            return Err(Error::BadRequest(
+                "Field 'priority' can't be negative.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn database_error(err: sqlx::Error) -> Error {
+    tracing::error!("Database error: {err}");
+    Error::InternalServerError("Internal database error".to_string())
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::post};
+
+use crate::features::requests::{handler::Handler, logic::Logic, repo::Repo};
+use crate::features::webhooks;
+
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+
+/// Создание заявок сотрудниками и интеграциями с правом `requests:create`.
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let dispatcher = webhooks::dispatcher(pool);
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo, dispatcher));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/requests", post(Handler::create_request))
+        .with_state(handler)
+}
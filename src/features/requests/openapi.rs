@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ErrorResponse, Request};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create_request),
+    components(schemas(Request, ErrorResponse)),
+    tags((name = "requests", description = "Заявки"))
+)]
+pub struct ApiDoc;
+
+/// Создание заявки
+///
+/// Обязательны `name` и `desired_at`. Доступно сотрудникам и API ключам с правом
+/// `requests:create`, например мониторингу. Заявка по ключу записывается на сотрудника,
+/// выпустившего ключ.
+#[utoipa::path(
+    post,
+    path = "/requests",
+    tag = "requests",
+    request_body = Request,
+    security(("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 201, description = "Заявка создана", body = Request),
+        (status = 400, description = "Некорректные поля, неизвестная или неактивная услуга", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права requests:create или у ключа нет владельца", body = ErrorResponse),
+        (status = 422, description = "Некорректное тело запроса"),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn create_request() {}
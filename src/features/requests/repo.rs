@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::models::dao::Request;
+use crate::models::dto;
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    /// Сотрудник, выпустивший API ключ. `None`, если ключа нет или сотрудник удалён.
+    #[tracing::instrument(name = "Request repo: api_key_owner", skip_all)]
+    pub async fn api_key_owner(&self, id: i64) -> Result<Option<i64>, sqlx::Error> {
+        tracing::debug!("Request repo: Getting owner of API key {id}");
+        let _timer = telemetry::query_timer("requests", "api_key_owner");
+        sqlx::query_scalar::<_, Option<i64>>("SELECT created_by FROM api_key WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map(Option::flatten)
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Активна ли услуга. `None`, если услуги нет.
+    #[tracing::instrument(name = "Request repo: service_active", skip_all)]
+    pub async fn service_active(&self, id: i64) -> Result<Option<bool>, sqlx::Error> {
+        tracing::debug!("Request repo: Checking service {id}");
+        let _timer = telemetry::query_timer("requests", "service_active");
+        sqlx::query_scalar("SELECT active FROM service WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Новая заявка в статусе 0.
+    #[tracing::instrument(name = "Request repo: insert", skip_all)]
+    pub async fn insert(
+        &self,
+        payload: &dto::Request,
+        owner_id: i64,
+    ) -> Result<Request, sqlx::Error> {
+        tracing::debug!("Request repo: Adding request {}", payload.name);
+        let _timer = telemetry::query_timer("requests", "insert");
+        sqlx::query_as::<_, Request>(
+            "INSERT INTO request (name, service_id, owner_id, priority, \"desc\", status, desired_at)
+            VALUES ($1, $2, $3, $4, $5, 0, $6)
+            RETURNING id, name, service_id::bigint, owner_id, priority, \"desc\" AS description,
+                status, desired_at, created_at, closed_at",
+        )
+        .bind(payload.name.trim())
+        .bind(payload.service_id.map(|id| id as i32))
+        .bind(owner_id)
+        .bind(payload.priority.unwrap_or_default())
+        .bind(payload.description.as_deref().unwrap_or_default())
+        .bind(payload.desired_at)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
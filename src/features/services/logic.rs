@@ -1,71 +1,166 @@
 use std::sync::Arc;
 
+use serde_json::json;
+
 use super::repo::Repo;
-use crate::models::dao;
-use crate::models::dto::{Error, Service};
+use crate::features::webhooks::Dispatcher;
+use crate::models::dao::{self, SkillLevel, WebhookEvent};
+use crate::models::dto::{Error, Service, ServiceFilter};
+
+/// Наибольшая длина описания услуги в символах.
+const MAX_DESCRIPTION_LEN: usize = 2000;
 
 pub struct Logic {
     repo: Arc<Repo>,
+    dispatcher: Dispatcher,
 }
 
 impl Logic {
-    pub fn new(repo: Arc<Repo>) -> Self {
-        Logic { repo: repo }
+    pub fn new(repo: Arc<Repo>, dispatcher: Dispatcher) -> Self {
+        Logic { repo, dispatcher }
     }
 
-    pub async fn create(&self, payload: Service) -> Result<Service, Error> {
-        tracing::debug!("Service logic: Creating service");
+    /// Проверка новой услуги. Используется также при импорте.
+    pub fn validate(payload: &Service) -> Result<(), Error> {
         if payload.name.is_empty() {
             tracing::error!("Field name is empty");
             return Err(Error::BadRequest(
                 "Field 'name' can't be empty.".to_string(),
             ));
         }
-        self.repo
-            .add_service(&payload.name)
+        Self::validate_metadata(payload)
+    }
+
+    /// Проверка описания, цены, длительности и уровня исполнителя.
+    fn validate_metadata(payload: &Service) -> Result<(), Error> {
+        let description_len = payload
+            .description
+            .as_ref()
+            .and_then(Option::as_deref)
+            .map_or(0, |d| d.chars().count());
+        if description_len > MAX_DESCRIPTION_LEN {
+            return Err(Error::BadRequest(format!(
+                "Field 'description' must be at most {} characters.",
+                MAX_DESCRIPTION_LEN
+            )));
+        }
+        // Оба поля переданы или оба нет, и оба со значением или оба `null`
+        let price_set = payload.base_price.map(|price| price.is_some());
+        if price_set != payload.currency.as_ref().map(|code| code.is_some()) {
+            return Err(Error::BadRequest(
+                "Fields 'base_price' and 'currency' must be set together.".to_string(),
+            ));
+        }
+        if payload.base_price.flatten().is_some_and(|price| price < 0) {
+            return Err(Error::BadRequest(
+                "Field 'base_price' can't be negative.".to_string(),
+            ));
+        }
+        let valid_currency =
+            |code: &str| code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase());
+        if payload
+            .currency
+            .as_ref()
+            .and_then(Option::as_deref)
+            .is_some_and(|code| !valid_currency(code))
+        {
+            return Err(Error::BadRequest(
+                "Field 'currency' must be an ISO 4217 code, e.g. RUB.".to_string(),
+            ));
+        }
+        if payload
+            .duration_minutes
+            .flatten()
+            .is_some_and(|minutes| minutes <= 0)
+        {
+            return Err(Error::BadRequest(
+                "Field 'duration_minutes' must be positive.".to_string(),
+            ));
+        }
+        if let Some(Some(level)) = &payload.skill_level {
+            SkillLevel::from(level)?;
+        }
+        Ok(())
+    }
+
+    /// Ошибка записи услуги: неизвестная категория, повтор названия или сбой базы.
+    pub fn write_error(err: sqlx::Error, payload: &Service) -> Error {
+        match err.as_database_error() {
+            Some(db) if db.is_foreign_key_violation() => Error::BadRequest(format!(
+                "Category with id: {} not found",
+                payload.category_id.flatten().unwrap_or_default()
+            )),
+            Some(db) if db.is_unique_violation() => {
+                Error::Conflict("Object already exists.".to_string())
+            }
+            _ => Error::InternalServerError("Internal database error".to_string()),
+        }
+    }
+
+    #[tracing::instrument(name = "Service logic: create", skip_all)]
+    pub async fn create(&self, payload: Service) -> Result<Service, Error> {
+        tracing::debug!("Service logic: Creating service");
+        Self::validate(&payload)?;
+        let service = self
+            .repo
+            .add_service(&payload)
             .await
-            .map(|model| dao::Service::to_dto(model))
-            .map_err(|_| Error::Conflict("Object already exists.".to_string()))
+            .map(dao::Service::to_dto)
+            .map_err(|err| Self::write_error(err, &payload))?;
+
+        self.dispatcher
+            .dispatch(WebhookEvent::ServiceCreated, json!(service));
+        Ok(service)
     }
 
-    pub async fn get_all(&self) -> Vec<Service> {
+    #[tracing::instrument(name = "Service logic: get_all", skip_all)]
+    pub async fn get_all(&self, filter: ServiceFilter) -> Vec<Service> {
         tracing::debug!("Service logic: Getting all services");
-        match self.repo.get_all_services().await {
-            Ok(v) => v
-                .into_iter()
-                .map(|elem| dao::Service::to_dto(elem))
-                .collect(),
+        match self.repo.get_all_services(&filter).await {
+            Ok(v) => v.into_iter().map(dao::Service::to_dto).collect(),
             Err(_) => Vec::<Service>::new(),
         }
     }
 
+    #[tracing::instrument(name = "Service logic: get_by_id", skip_all)]
     pub async fn get_by_id(&self, id: i64) -> Result<Service, Error> {
         tracing::debug!("Service logic: Getting service by id");
-        let result = self
-            .repo
+
+        self.repo
             .get_by_id(id)
             .await
-            .map(|model| dao::Service::to_dto(model))
-            .map_err(|_| Error::NotFound(format!("Service with id: {} not found", id)));
-        return result;
+            .map(dao::Service::to_dto)
+            .map_err(|_| Error::NotFound(format!("Service with id: {} not found", id)))
     }
 
+    /// Замена названия. Категория и метаданные меняются только переданные,
+    /// `null` в `category_id` убирает услугу из категории, см. `Repo::update_by_id`.
+    #[tracing::instrument(name = "Service logic: put_by_id", skip_all)]
     pub async fn put_by_id(&self, id: i64, payload: Service) -> Result<Service, Error> {
         tracing::debug!("Service logic: Updating service by id");
         if payload.name.is_empty() {
             return Err(Error::BadRequest("Field name can't be empty".to_string()));
         }
+        Self::validate_metadata(&payload)?;
 
-        let result = self
+        let service = self
             .repo
-            .update_by_id(id, payload.name)
+            .update_by_id(id, &payload)
             .await
-            .map(|model| dao::Service::to_dto(model))
-            .map_err(|_| Error::NotFound(format!("Service with id: {} not found", id)));
+            .map(dao::Service::to_dto)
+            .map_err(|err| match err {
+                sqlx::Error::RowNotFound => {
+                    Error::NotFound(format!("Service with id: {} not found", id))
+                }
+                _ => Self::write_error(err, &payload),
+            })?;
 
-        return result;
+        self.dispatcher
+            .dispatch(WebhookEvent::ServiceUpdated, json!(service));
+        Ok(service)
     }
 
+    #[tracing::instrument(name = "Service logic: delete_by_id", skip_all)]
     pub async fn delete_by_id(&self, id: i64) -> Result<i64, Error> {
         tracing::debug!("Service logic: Deleting service by id");
         let result = self.repo.delete_by_id(id).await;
@@ -73,6 +168,8 @@ impl Logic {
         match result {
             Ok(rows) => {
                 if rows > 0 {
+                    self.dispatcher
+                        .dispatch(WebhookEvent::ServiceDeleted, json!({ "id": id }));
                     Ok(id)
                 } else {
                     tracing::error!("Database error: Service not found by id {}", id);
@@ -84,9 +181,9 @@ impl Logic {
             }
             Err(err) => {
                 tracing::error!("Database error: {err}");
-                Err(Error::InternalServerError(format!(
-                    "Internal database error"
-                )))
+                Err(Error::InternalServerError(
+                    "Internal database error".to_string(),
+                ))
             }
         }
     }
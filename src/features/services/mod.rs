@@ -6,15 +6,23 @@ use axum::{
 };
 
 use crate::features::services::{handler::Handler, logic::Logic, repo::Repo};
+use crate::features::webhooks;
 
 pub mod handler;
 pub mod logic;
+pub mod openapi;
 pub mod repo;
 
 pub fn new(pool: &sqlx::PgPool) -> Router {
+    with_read_pool(pool, pool)
+}
+
+/// Как `new`, но списки читаются из `read_pool`, а записи идут в `pool`.
+pub fn with_read_pool(pool: &sqlx::PgPool, read_pool: &sqlx::PgPool) -> Router {
+    let dispatcher = webhooks::dispatcher(pool);
     let pool = Arc::new(pool.clone());
-    let repo = Arc::new(Repo::new(pool));
-    let logic = Arc::new(Logic::new(repo));
+    let repo = Arc::new(Repo::new(pool).with_read_pool(Arc::new(read_pool.clone())));
+    let logic = Arc::new(Logic::new(repo, dispatcher));
     let handler = Arc::new(Handler::new(logic));
 
     Router::new()
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI. Обработчики - методы `Handler`,
+// а `utoipa::path` работает только со свободными функциями, поэтому здесь заглушки.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ErrorResponse, Service, ServiceFilter};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create_service, get_services, get_service_by_id, update_service, delete_service),
+    components(schemas(Service, ErrorResponse)),
+    tags((name = "services", description = "Каталог услуг"))
+)]
+pub struct ApiDoc;
+
+/// Создание услуги
+#[utoipa::path(
+    post,
+    path = "/services",
+    tag = "services",
+    request_body = Service,
+    responses(
+        (status = 201, description = "Услуга создана", body = Service),
+        (status = 400, description = "Некорректные поля или неизвестная категория", body = ErrorResponse),
+        (status = 409, description = "Услуга с таким названием уже есть", body = ErrorResponse),
+        (status = 422, description = "Некорректное тело запроса"),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn create_service() {}
+
+/// Список услуг, с `category_id` - только услуги категории и её подкатегорий
+#[utoipa::path(
+    get,
+    path = "/services",
+    tag = "services",
+    params(ServiceFilter),
+    responses((status = 200, description = "Список услуг", body = Vec<Service>))
+)]
+fn get_services() {}
+
+/// Услуга по id
+#[utoipa::path(
+    get,
+    path = "/services/{id}",
+    tag = "services",
+    params(("id" = i64, Path, description = "Id услуги")),
+    responses(
+        (status = 200, description = "Услуга", body = Service),
+        (status = 404, description = "Услуга не найдена", body = ErrorResponse)
+    )
+)]
+fn get_service_by_id() {}
+
+/// Изменение услуги
+///
+/// Поля, которых нет в теле, кроме названия, остаются прежними, `null` очищает значение.
+#[utoipa::path(
+    put,
+    path = "/services/{id}",
+    tag = "services",
+    params(("id" = i64, Path, description = "Id услуги")),
+    request_body = Service,
+    responses(
+        (status = 200, description = "Услуга изменена", body = Service),
+        (status = 400, description = "Некорректные поля или неизвестная категория", body = ErrorResponse),
+        (status = 404, description = "Услуга не найдена", body = ErrorResponse),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn update_service() {}
+
+/// Удаление услуги
+#[utoipa::path(
+    delete,
+    path = "/services/{id}",
+    tag = "services",
+    params(("id" = i64, Path, description = "Id услуги")),
+    responses(
+        (status = 200, description = "Id удалённой услуги", body = Object, example = json!({"id": 1})),
+        (status = 404, description = "Услуга не найдена", body = ErrorResponse),
+        (status = 500, description = "Ошибка базы данных", body = ErrorResponse)
+    )
+)]
+fn delete_service() {}
@@ -1,26 +1,69 @@
 use std::{error::Error, sync::Arc};
 
 use crate::models::dao::Service;
-use sqlx::PgPool;
+use crate::models::dto;
+use crate::telemetry;
+use sqlx::{PgExecutor, PgPool};
+
+/// Услуги по `dto::ServiceFilter`: `$1` - категория вместе с подкатегориями, `$2` -
+/// признак активности. Общий для списка и выгрузки, порядок задаёт вызывающий.
+pub const FILTERED_SERVICES: &str = "WITH RECURSIVE subtree AS (
+        SELECT id FROM category WHERE id = $1
+        UNION ALL
+        SELECT c.id FROM category c JOIN subtree s ON c.parent_id = s.id
+    )
+    SELECT * FROM service
+    WHERE ($1 IS NULL OR category_id IN (SELECT id FROM subtree))
+        AND ($2::boolean IS NULL OR active = $2)";
 
 pub struct Repo {
     _pool: Arc<PgPool>,
+    read_pool: Arc<PgPool>,
 }
 
 impl Repo {
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Repo { _pool: pool }
+        Repo {
+            read_pool: pool.clone(),
+            _pool: pool,
+        }
+    }
+
+    /// Пул для списков, обычно реплика. По умолчанию используется основной пул.
+    pub fn with_read_pool(mut self, read_pool: Arc<PgPool>) -> Self {
+        self.read_pool = read_pool;
+        self
     }
 
-    pub async fn add_service<'a>(&self, name: &'a str) -> Result<Service, Box<dyn Error>> {
-        tracing::debug!("Service repo: Adding service with name: {}", name);
+    #[tracing::instrument(name = "Service repo: add_service", skip_all)]
+    pub async fn add_service(&self, payload: &dto::Service) -> Result<Service, sqlx::Error> {
+        let _timer = telemetry::query_timer("services", "add_service");
+        Self::insert(&*self._pool, payload).await
+    }
+
+    /// Вставка через любой исполнитель запросов, в том числе внутри транзакции.
+    #[tracing::instrument(name = "Service repo: insert", skip_all)]
+    pub async fn insert<'e, E: PgExecutor<'e>>(
+        executor: E,
+        payload: &dto::Service,
+    ) -> Result<Service, sqlx::Error> {
+        tracing::debug!("Service repo: Adding service with name: {}", payload.name);
+        let _timer = telemetry::query_timer("services", "insert");
         let row = sqlx::query_as(
-            "INSERT INTO service (name)
-            VALUES ($1)
+            "INSERT INTO service (name, category_id, description, base_price, currency,
+                duration_minutes, skill_level, active)
+            VALUES ($1, $2, NULLIF($3, ''), $4, $5, $6, $7, COALESCE($8, TRUE))
             RETURNING *",
         )
-        .bind(name)
-        .fetch_one(&*self._pool)
+        .bind(&payload.name)
+        .bind(payload.category_id.flatten())
+        .bind(payload.description.as_ref().and_then(Option::as_deref))
+        .bind(payload.base_price.flatten())
+        .bind(payload.currency.as_ref().and_then(Option::as_deref))
+        .bind(payload.duration_minutes.flatten())
+        .bind(payload.skill_level.as_ref().and_then(Option::as_deref))
+        .bind(payload.active)
+        .fetch_one(executor)
         .await
         .map_err(|err| {
             tracing::error!("Database error: {err}");
@@ -31,10 +74,19 @@ impl Repo {
         Ok(row)
     }
 
-    pub async fn get_all_services(&self) -> Result<Vec<Service>, Box<dyn Error>> {
+    /// Все услуги или услуги категории `category_id` вместе с подкатегориями,
+    /// при заданном `active` - только с этим признаком.
+    #[tracing::instrument(name = "Service repo: get_all_services", skip_all)]
+    pub async fn get_all_services(
+        &self,
+        filter: &dto::ServiceFilter,
+    ) -> Result<Vec<Service>, Box<dyn Error>> {
         tracing::debug!("Service repo: Getting vector services");
-        let row = sqlx::query_as("SELECT * FROM service")
-            .fetch_all(&*self._pool)
+        let _timer = telemetry::query_timer("services", "get_all_services");
+        let row = sqlx::query_as(FILTERED_SERVICES)
+            .bind(filter.category_id)
+            .bind(filter.active)
+            .fetch_all(&*self.read_pool)
             .await
             .map_err(|err| {
                 tracing::error!("Database error: {err}");
@@ -45,8 +97,10 @@ impl Repo {
         Ok(row)
     }
 
+    #[tracing::instrument(name = "Service repo: get_by_id", skip_all)]
     pub async fn get_by_id(&self, id: i64) -> Result<Service, Box<dyn Error>> {
         tracing::debug!("Service repo: Getting service by id = {}", id);
+        let _timer = telemetry::query_timer("services", "get_by_id");
         let row = sqlx::query_as::<_, Service>("SELECT * FROM service WHERE id = $1")
             .bind(id)
             .fetch_one(&*self._pool)
@@ -55,21 +109,52 @@ impl Repo {
         match row {
             Ok(obj) => {
                 tracing::debug!("Get service successfully");
-                return Ok(obj);
+                Ok(obj)
             }
             Err(err) => {
                 tracing::error!("Database error: {err}");
-                return Err(err.into());
+                Err(err.into())
             }
         }
     }
 
-    pub async fn update_by_id(&self, id: i64, name: String) -> Result<Service, sqlx::Error> {
+    /// Изменение услуги. Незаданные категория и метаданные сохраняют прежние значения,
+    /// `null` очищает их. Пустое описание, как и при создании, сохраняется как `NULL`.
+    #[tracing::instrument(name = "Service repo: update_by_id", skip_all)]
+    pub async fn update_by_id(
+        &self,
+        id: i64,
+        payload: &dto::Service,
+    ) -> Result<Service, sqlx::Error> {
         tracing::debug!("Service repo: Updating service by id = {}", id);
+        let _timer = telemetry::query_timer("services", "update_by_id");
         let row = sqlx::query_as::<_, Service>(
-            "UPDATE service SET name = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+            "UPDATE service SET name = $1,
+                category_id = CASE WHEN $2 THEN $3 ELSE category_id END,
+                description = CASE WHEN $4 THEN NULLIF($5, '') ELSE description END,
+                base_price = CASE WHEN $6 THEN $7 ELSE base_price END,
+                currency = CASE WHEN $8 THEN $9 ELSE currency END,
+                duration_minutes = CASE WHEN $10 THEN $11 ELSE duration_minutes END,
+                skill_level = CASE WHEN $12 THEN $13 ELSE skill_level END,
+                active = COALESCE($14, active),
+                updated_at = NOW()
+            WHERE id = $15
+            RETURNING *",
         )
-        .bind(name)
+        .bind(&payload.name)
+        .bind(payload.category_id.is_some())
+        .bind(payload.category_id.flatten())
+        .bind(payload.description.is_some())
+        .bind(payload.description.as_ref().and_then(Option::as_deref))
+        .bind(payload.base_price.is_some())
+        .bind(payload.base_price.flatten())
+        .bind(payload.currency.is_some())
+        .bind(payload.currency.as_ref().and_then(Option::as_deref))
+        .bind(payload.duration_minutes.is_some())
+        .bind(payload.duration_minutes.flatten())
+        .bind(payload.skill_level.is_some())
+        .bind(payload.skill_level.as_ref().and_then(Option::as_deref))
+        .bind(payload.active)
         .bind(id)
         .fetch_one(&*self._pool)
         .await;
@@ -77,17 +162,19 @@ impl Repo {
         match row {
             Ok(obj) => {
                 tracing::debug!("Update service successfully");
-                return Ok(obj);
+                Ok(obj)
             }
             Err(err) => {
                 tracing::error!("Database error: {err}");
-                return Err(err.into());
+                Err(err)
             }
         }
     }
 
+    #[tracing::instrument(name = "Service repo: delete_by_id", skip_all)]
     pub async fn delete_by_id(&self, id: i64) -> Result<u64, sqlx::Error> {
         tracing::debug!("Service repo: Deleting service by id = {}", id);
+        let _timer = telemetry::query_timer("services", "delete_by_id");
         let result = sqlx::query("DELETE FROM service WHERE id = $1")
             .bind(id)
             .execute(&*self._pool)
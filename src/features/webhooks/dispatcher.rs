@@ -0,0 +1,181 @@
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+use super::repo::Repo;
+use super::target::PublicResolver;
+use crate::models::dao::{Webhook, WebhookEvent};
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+
+// Общие для всех экземпляров `Dispatcher`: сервисы и импорт создают свои копии,
+// а при остановке нужно дождаться доставок от всех.
+static TASKS: LazyLock<TaskTracker> = LazyLock::new(TaskTracker::new);
+static STOPPING: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+
+/// Рассылает события подписчикам в фоне.
+///
+/// Каждая доставка подписывается HMAC-SHA256 секретом подписки, при ошибке
+/// повторяется с экспоненциальной задержкой, а каждая попытка пишется в `webhook_delivery`.
+#[derive(Clone)]
+pub struct Dispatcher {
+    repo: Arc<Repo>,
+    client: reqwest::Client,
+    max_attempts: i32,
+    backoff: Duration,
+}
+
+impl Dispatcher {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Dispatcher {
+            repo,
+            // Перенаправление могло бы увести запрос во внутреннюю сеть в обход проверок
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicResolver))
+                .build()
+                .unwrap_or_default(),
+            max_attempts: 5,
+            backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Количество попыток доставки и задержка перед второй попыткой (далее удваивается).
+    pub fn with_retry(mut self, max_attempts: i32, backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn dispatch(&self, event: WebhookEvent, data: Value) -> JoinHandle<()> {
+        let dispatcher = self.clone();
+        TASKS.spawn(async move {
+            let webhooks = match dispatcher.repo.get_subscribed(event.code()).await {
+                Ok(v) => v,
+                Err(err) => {
+                    tracing::error!("Failed to load webhooks for {}: {}", event.code(), err);
+                    return;
+                }
+            };
+
+            let body = json!({
+                "event": event.code(),
+                "timestamp": Utc::now(),
+                "data": data,
+            })
+            .to_string();
+
+            let deliveries = webhooks.into_iter().map(|webhook| {
+                let dispatcher = dispatcher.clone();
+                let body = body.clone();
+                tokio::spawn(async move { dispatcher.deliver(&webhook, event, &body).await })
+            });
+            for delivery in deliveries.collect::<Vec<_>>() {
+                let _ = delivery.await;
+            }
+        })
+    }
+
+    async fn deliver(&self, webhook: &Webhook, event: WebhookEvent, body: &str) {
+        let signature = sign(&webhook.secret, body);
+
+        for attempt in 1..=self.max_attempts {
+            let result = self
+                .client
+                .post(&webhook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event.code())
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.to_string())
+                .send()
+                .await;
+
+            let (status_code, success, error) = match result {
+                Ok(response) => {
+                    let status = response.status();
+                    let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+                    (Some(status.as_u16() as i32), status.is_success(), error)
+                }
+                Err(err) => (None, false, Some(err.to_string())),
+            };
+
+            if let Err(err) = self
+                .repo
+                .add_delivery(
+                    webhook.id,
+                    event.code(),
+                    body,
+                    attempt,
+                    status_code,
+                    success,
+                    error,
+                )
+                .await
+            {
+                tracing::error!("Failed to log webhook delivery: {}", err);
+            }
+
+            if success {
+                tracing::debug!("Webhook {} delivered on attempt {}", webhook.id, attempt);
+                return;
+            }
+
+            if attempt < self.max_attempts {
+                let delay = self.backoff * 2u32.pow((attempt - 1) as u32);
+                tracing::warn!(
+                    "Webhook {} delivery attempt {} failed, retrying in {:?}",
+                    webhook.id,
+                    attempt,
+                    delay
+                );
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = STOPPING.cancelled() => {
+                        tracing::warn!(
+                            "Webhook {} delivery abandoned on shutdown after attempt {}",
+                            webhook.id,
+                            attempt
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+
+        tracing::error!(
+            "Webhook {} delivery failed after {} attempts",
+            webhook.id,
+            self.max_attempts
+        );
+    }
+}
+
+/// Останавливает рассылку: повторные попытки больше не планируются, текущие
+/// доставки получают `timeout` на завершение.
+///
+/// Возвращает число задач, не успевших завершиться.
+pub async fn stop(timeout: Duration) -> usize {
+    STOPPING.cancel();
+    TASKS.close();
+    if tokio::time::timeout(timeout, TASKS.wait()).await.is_err() {
+        tracing::warn!("Webhook deliveries did not finish within {:?}", timeout);
+    }
+    TASKS.len()
+}
+
+/// Подпись тела запроса в формате `sha256=<hex>`.
+pub fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use tracing::Instrument;
+
+use super::logic::Logic;
+use crate::features::auth::Principal;
+use crate::models::dao::Permission;
+use crate::models::dto::Webhook;
+
+pub struct Handler {
+    logic: Arc<Logic>,
+}
+
+impl Handler {
+    pub fn new(logic: Arc<Logic>) -> Self {
+        Handler { logic }
+    }
+
+    pub async fn create_webhook(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Json(payload): Json<Webhook>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: create_webhook", url = ?payload.url);
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.create(payload).await {
+                Ok(result) => {
+                    tracing::debug!("Webhook created successfully: {:?}", result);
+                    (StatusCode::CREATED, Json(json!(result)))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to create webhook: {:?}", err);
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_webhooks(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: get_webhooks");
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.get_all().await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get webhooks");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_webhook_by_id(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: get_webhook_by_id with ", id);
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.get_by_id(id).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get webhook by id");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn update_webhook(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+        Json(payload): Json<Webhook>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: update_webhook with ", id);
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.put_by_id(id, payload).await {
+                Ok(result) => {
+                    tracing::debug!("Put webhook by id successfully");
+                    (StatusCode::OK, Json(json!(result)))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to put webhook by id");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn delete_webhook(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: delete_webhook with ", id);
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.delete_by_id(id).await {
+                Ok(result) => {
+                    tracing::debug!("Delete webhook by id successfully");
+                    (StatusCode::OK, Json(json!({"id": result})))
+                }
+                Err(err) => {
+                    tracing::error!("Failed to delete webhook by id");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    pub async fn get_deliveries(
+        principal: Principal,
+        State(handler): State<Arc<Handler>>,
+        Path(id): Path<i64>,
+    ) -> (StatusCode, Json<Value>) {
+        let span = tracing::info_span!("Webhook handler: get_deliveries with ", id);
+        async {
+            if let Err(err) = principal.require(Permission::WebhooksManage) {
+                let (status, Json(error_response)) = err.into_response();
+                return (status, Json(json!(error_response)));
+            }
+            match handler.logic.get_deliveries(id).await {
+                Ok(result) => (StatusCode::OK, Json(json!(result))),
+                Err(err) => {
+                    tracing::error!("Failed to get webhook deliveries");
+                    let (status, Json(error_response)) = err.into_response();
+                    (status, Json(json!(error_response)))
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
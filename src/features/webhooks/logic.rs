@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use reqwest::Url;
+
+use super::repo::Repo;
+use super::target;
+use crate::models::dao;
+use crate::models::dto::{Error, Webhook, WebhookDelivery};
+
+pub struct Logic {
+    repo: Arc<Repo>,
+}
+
+impl Logic {
+    pub fn new(repo: Arc<Repo>) -> Self {
+        Logic { repo }
+    }
+
+    #[tracing::instrument(name = "Webhook logic: create", skip_all)]
+    pub async fn create(&self, payload: Webhook) -> Result<Webhook, Error> {
+        tracing::debug!("Webhook logic: Creating webhook");
+        let url = Self::validate_url(payload.url)?;
+        let events = Self::validate_events(payload.events)?;
+        let secret = match payload.secret {
+            Some(secret) if !secret.is_empty() => secret,
+            _ => {
+                return Err(Error::BadRequest(
+                    "Field 'secret' can't be empty.".to_string(),
+                ));
+            }
+        };
+
+        self.repo
+            .create(url, secret, events, payload.active.unwrap_or(true))
+            .await
+            .map(dao::Webhook::to_dto)
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "Webhook logic: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<Webhook>, Error> {
+        tracing::debug!("Webhook logic: Getting all webhooks");
+        self.repo
+            .get_all()
+            .await
+            .map(|v| v.into_iter().map(dao::Webhook::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    #[tracing::instrument(name = "Webhook logic: get_by_id", skip_all)]
+    pub async fn get_by_id(&self, id: i64) -> Result<Webhook, Error> {
+        tracing::debug!("Webhook logic: Getting webhook by id");
+        self.repo
+            .get_by_id(id)
+            .await
+            .map(dao::Webhook::to_dto)
+            .map_err(|_| Error::NotFound(format!("Webhook with id: {} not found", id)))
+    }
+
+    #[tracing::instrument(name = "Webhook logic: put_by_id", skip_all)]
+    pub async fn put_by_id(&self, id: i64, payload: Webhook) -> Result<Webhook, Error> {
+        tracing::debug!("Webhook logic: Updating webhook by id");
+        let url = Self::validate_url(payload.url)?;
+        let events = Self::validate_events(payload.events)?;
+        let secret = payload.secret.filter(|secret| !secret.is_empty());
+
+        self.repo
+            .update_by_id(id, url, secret, events, payload.active)
+            .await
+            .map(dao::Webhook::to_dto)
+            .map_err(|_| Error::NotFound(format!("Webhook with id: {} not found", id)))
+    }
+
+    #[tracing::instrument(name = "Webhook logic: delete_by_id", skip_all)]
+    pub async fn delete_by_id(&self, id: i64) -> Result<i64, Error> {
+        tracing::debug!("Webhook logic: Deleting webhook by id");
+        match self.repo.delete_by_id(id).await {
+            Ok(rows) if rows > 0 => Ok(id),
+            Ok(_) => Err(Error::NotFound(format!(
+                "Webhook with id: {} not found",
+                id
+            ))),
+            Err(err) => {
+                tracing::error!("Database error: {err}");
+                Err(Error::InternalServerError(
+                    "Internal database error".to_string(),
+                ))
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "Webhook logic: get_deliveries", skip_all)]
+    pub async fn get_deliveries(&self, id: i64) -> Result<Vec<WebhookDelivery>, Error> {
+        tracing::debug!("Webhook logic: Getting deliveries for webhook");
+        self.get_by_id(id).await?;
+        self.repo
+            .get_deliveries(id)
+            .await
+            .map(|v| v.into_iter().map(dao::WebhookDelivery::to_dto).collect())
+            .map_err(|_| Error::InternalServerError("Internal database error".to_string()))
+    }
+
+    fn validate_url(url: Option<String>) -> Result<String, Error> {
+        let Some(url) = url else {
+            return Err(Error::BadRequest("Field 'url' can't be empty.".to_string()));
+        };
+        let parsed = match Url::parse(&url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => parsed,
+            _ => {
+                return Err(Error::BadRequest(
+                    "Field 'url' must be an http(s) URL.".to_string(),
+                ));
+            }
+        };
+        if !target::is_public_url(&parsed) {
+            tracing::error!("Webhook url {} points to a non-public host", url);
+            return Err(Error::BadRequest(
+                "Field 'url' must point to a public host.".to_string(),
+            ));
+        }
+        Ok(url)
+    }
+
+    fn validate_events(events: Option<Vec<String>>) -> Result<Vec<String>, Error> {
+        let events = events.unwrap_or_default();
+        if events.is_empty() {
+            return Err(Error::BadRequest(
+                "Field 'events' can't be empty.".to_string(),
+            ));
+        }
+
+        let mut codes = Vec::<String>::new();
+        for event in events {
+            let code = dao::WebhookEvent::from(&event)?.code().to_string();
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        Ok(codes)
+    }
+}
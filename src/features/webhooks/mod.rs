@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::features::webhooks::{handler::Handler, logic::Logic, repo::Repo};
+
+pub mod dispatcher;
+pub mod handler;
+pub mod logic;
+pub mod openapi;
+pub mod repo;
+pub mod target;
+
+pub use dispatcher::Dispatcher;
+
+pub fn new(pool: &sqlx::PgPool) -> Router {
+    let pool = Arc::new(pool.clone());
+    let repo = Arc::new(Repo::new(pool));
+    let logic = Arc::new(Logic::new(repo));
+    let handler = Arc::new(Handler::new(logic));
+
+    Router::new()
+        .route("/webhooks", post(Handler::create_webhook))
+        .route("/webhooks", get(Handler::get_webhooks))
+        .route("/webhooks/{id}", get(Handler::get_webhook_by_id))
+        .route("/webhooks/{id}", put(Handler::update_webhook))
+        .route("/webhooks/{id}", delete(Handler::delete_webhook))
+        .route("/webhooks/{id}/deliveries", get(Handler::get_deliveries))
+        .with_state(handler)
+}
+
+/// Диспетчер событий поверх того же пула, что и у остальных фич.
+pub fn dispatcher(pool: &sqlx::PgPool) -> Dispatcher {
+    Dispatcher::new(Arc::new(Repo::new(Arc::new(pool.clone()))))
+}
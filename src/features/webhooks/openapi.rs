@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+// Описание маршрутов из `super::new` для OpenAPI, см. `features::services::openapi`.
+
+use utoipa::OpenApi;
+
+use crate::models::dto::{ErrorResponse, Webhook, WebhookDelivery};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_webhook,
+        get_webhooks,
+        get_webhook_by_id,
+        update_webhook,
+        delete_webhook,
+        get_deliveries
+    ),
+    components(schemas(Webhook, WebhookDelivery, ErrorResponse)),
+    tags((name = "webhooks", description = "Подписки внешних систем на события"))
+)]
+pub struct ApiDoc;
+
+/// Создание подписки
+///
+/// Адрес должен вести на публичный хост: локальные, внутренние и частные адреса
+/// отклоняются. Доставки подписываются заголовком `X-Webhook-Signature: sha256=<hex>`
+/// (HMAC-SHA256 тела запроса секретом подписки).
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    request_body = Webhook,
+    responses(
+        (status = 201, description = "Подписка создана", body = Webhook),
+        (status = 400, description = "Некорректный или непубличный url, секрет или событие", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn create_webhook() {}
+
+/// Список подписок
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "Список подписок", body = Vec<Webhook>),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn get_webhooks() {}
+
+/// Подписка по id
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = i64, Path, description = "Id подписки")),
+    responses(
+        (status = 200, description = "Подписка", body = Webhook),
+        (status = 404, description = "Подписка не найдена", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn get_webhook_by_id() {}
+
+/// Изменение подписки
+///
+/// Если `secret` или `active` не переданы, остаются прежними.
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = i64, Path, description = "Id подписки")),
+    request_body = Webhook,
+    responses(
+        (status = 200, description = "Подписка изменена", body = Webhook),
+        (status = 400, description = "Некорректный или непубличный url или событие", body = ErrorResponse),
+        (status = 404, description = "Подписка не найдена", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn update_webhook() {}
+
+/// Удаление подписки
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = i64, Path, description = "Id подписки")),
+    responses(
+        (status = 200, description = "Id удалённой подписки", body = Object, example = json!({"id": 1})),
+        (status = 404, description = "Подписка не найдена", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn delete_webhook() {}
+
+/// Журнал попыток доставки, новые первыми
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    security(("bearer" = []), ("api_key" = [])),
+    params(("id" = i64, Path, description = "Id подписки")),
+    responses(
+        (status = 200, description = "Попытки доставки", body = Vec<WebhookDelivery>),
+        (status = 404, description = "Подписка не найдена", body = ErrorResponse),
+        (status = 401, description = "Нет токена или API ключа", body = ErrorResponse),
+        (status = 403, description = "Нет права webhooks:manage", body = ErrorResponse)
+    )
+)]
+fn get_deliveries() {}
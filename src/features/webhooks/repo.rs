@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::models::dao::{Webhook, WebhookDelivery};
+use crate::telemetry;
+
+pub struct Repo {
+    pool: Arc<PgPool>,
+}
+
+impl Repo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Repo { pool }
+    }
+
+    #[tracing::instrument(name = "Webhook repo: create", skip_all)]
+    pub async fn create(
+        &self,
+        url: String,
+        secret: String,
+        events: Vec<String>,
+        active: bool,
+    ) -> Result<Webhook, sqlx::Error> {
+        tracing::debug!("Webhook repo: Adding webhook for url: {}", url);
+        let _timer = telemetry::query_timer("webhooks", "create");
+        sqlx::query_as::<_, Webhook>(
+            "INSERT INTO webhook (url, secret, events, active)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+        )
+        .bind(url)
+        .bind(secret)
+        .bind(events)
+        .bind(active)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Webhook repo: get_all", skip_all)]
+    pub async fn get_all(&self) -> Result<Vec<Webhook>, sqlx::Error> {
+        tracing::debug!("Webhook repo: Getting vector webhooks");
+        let _timer = telemetry::query_timer("webhooks", "get_all");
+        sqlx::query_as::<_, Webhook>("SELECT * FROM webhook ORDER BY id")
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    #[tracing::instrument(name = "Webhook repo: get_by_id", skip_all)]
+    pub async fn get_by_id(&self, id: i64) -> Result<Webhook, sqlx::Error> {
+        tracing::debug!("Webhook repo: Getting webhook by id = {}", id);
+        let _timer = telemetry::query_timer("webhooks", "get_by_id");
+        sqlx::query_as::<_, Webhook>("SELECT * FROM webhook WHERE id = $1")
+            .bind(id)
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|err| {
+                tracing::error!("Database error: {err}");
+                err
+            })
+    }
+
+    /// Активные подписки, у которых в списке событий есть `event`.
+    #[tracing::instrument(name = "Webhook repo: get_subscribed", skip_all)]
+    pub async fn get_subscribed(&self, event: &str) -> Result<Vec<Webhook>, sqlx::Error> {
+        tracing::debug!("Webhook repo: Getting webhooks subscribed to {}", event);
+        let _timer = telemetry::query_timer("webhooks", "get_subscribed");
+        sqlx::query_as::<_, Webhook>(
+            "SELECT * FROM webhook WHERE active AND $1 = ANY(events) ORDER BY id",
+        )
+        .bind(event)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Webhook repo: update_by_id", skip_all)]
+    pub async fn update_by_id(
+        &self,
+        id: i64,
+        url: String,
+        secret: Option<String>,
+        events: Vec<String>,
+        active: Option<bool>,
+    ) -> Result<Webhook, sqlx::Error> {
+        tracing::debug!("Webhook repo: Updating webhook by id = {}", id);
+        let _timer = telemetry::query_timer("webhooks", "update_by_id");
+        sqlx::query_as::<_, Webhook>(
+            "UPDATE webhook
+            SET url = $1, secret = COALESCE($2, secret), events = $3, active = COALESCE($4, active),
+                updated_at = NOW()
+            WHERE id = $5
+            RETURNING *",
+        )
+        .bind(url)
+        .bind(secret)
+        .bind(events)
+        .bind(active)
+        .bind(id)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Webhook repo: delete_by_id", skip_all)]
+    pub async fn delete_by_id(&self, id: i64) -> Result<u64, sqlx::Error> {
+        tracing::debug!("Webhook repo: Deleting webhook by id = {}", id);
+        let _timer = telemetry::query_timer("webhooks", "delete_by_id");
+        let result = sqlx::query("DELETE FROM webhook WHERE id = $1")
+            .bind(id)
+            .execute(&*self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(name = "Webhook repo: add_delivery", skip_all)]
+    pub async fn add_delivery(
+        &self,
+        webhook_id: i64,
+        event: &str,
+        payload: &str,
+        attempt: i32,
+        status_code: Option<i32>,
+        success: bool,
+        error: Option<String>,
+    ) -> Result<WebhookDelivery, sqlx::Error> {
+        tracing::debug!(
+            "Webhook repo: Logging delivery attempt {} for webhook {}",
+            attempt,
+            webhook_id
+        );
+        let _timer = telemetry::query_timer("webhooks", "add_delivery");
+        sqlx::query_as::<_, WebhookDelivery>(
+            "INSERT INTO webhook_delivery (webhook_id, event, payload, attempt, status_code, success, error)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *",
+        )
+        .bind(webhook_id)
+        .bind(event)
+        .bind(payload)
+        .bind(attempt)
+        .bind(status_code)
+        .bind(success)
+        .bind(error)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+
+    #[tracing::instrument(name = "Webhook repo: get_deliveries", skip_all)]
+    pub async fn get_deliveries(
+        &self,
+        webhook_id: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        tracing::debug!(
+            "Webhook repo: Getting deliveries for webhook {}",
+            webhook_id
+        );
+        let _timer = telemetry::query_timer("webhooks", "get_deliveries");
+        sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT * FROM webhook_delivery WHERE webhook_id = $1 ORDER BY created_at DESC, id DESC",
+        )
+        .bind(webhook_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|err| {
+            tracing::error!("Database error: {err}");
+            err
+        })
+    }
+}
@@ -0,0 +1,89 @@
+//! Куда можно доставлять вебхуки. Адрес задаёт пользователь API, поэтому без проверки
+//! приложение само отправляло бы запросы во внутреннюю сеть: к базе, метаданным облака
+//! (`169.254.169.254`), сервисам кластера.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+// Имена, которые резолвятся только внутри сети или кластера
+const INTERNAL_SUFFIXES: &[&str] = &[".localhost", ".local", ".internal", ".svc"];
+
+/// Проверка адреса подписки. IP в адресе должен быть публичным, имя хоста - не
+/// внутренним. Во что имя резолвится, проверяет `PublicResolver` при доставке.
+pub fn is_public_url(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    // IPv6 в адресе записывается в скобках
+    match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => is_public_domain(host),
+    }
+}
+
+fn is_public_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    // Имя из одной части (`postgres`, `redis`) - сервис из локального поиска DNS
+    domain.contains('.')
+        && domain != "localhost"
+        && !INTERNAL_SUFFIXES
+            .iter()
+            .any(|suffix| domain.ends_with(suffix))
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || a == 0
+        // 100.64.0.0/10, адреса за NAT провайдера и в некоторых кластерах
+        || (a == 100 && (64..128).contains(&b)))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7, уникальные локальные
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10, локальные для канала
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// DNS для доставки: отбрасывает непубличные адреса. Иначе имя, прошедшее проверку при
+/// подписке, могло бы позже начать резолвиться во внутреннюю сеть.
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(
+                    format!("{} resolves only to non-public addresses", name.as_str()).into(),
+                );
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
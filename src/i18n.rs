@@ -0,0 +1,204 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use axum::extract::Request;
+use axum::http::{HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Язык сообщений API и писем.
+///
+/// Сообщения в коде пишутся по-английски и служат ключами каталогов `locales/*.toml`,
+/// перевод выполняется при формировании ответа, см. `negotiate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Locale {
+    En = 0,
+    Ru = 1,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ru];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ru => "ru",
+        }
+    }
+
+    /// Перевод сообщения. Сообщения без перевода возвращаются как есть.
+    pub fn translate(&self, message: &str) -> String {
+        CATALOGS[*self as usize].translate(message)
+    }
+
+    /// Перевод шаблона с `{}` и подстановка `args` по порядку.
+    pub fn format(&self, template: &str, args: &[&dyn Display]) -> String {
+        fill(
+            &self.translate(template),
+            args.iter().map(|arg| arg.to_string()),
+        )
+    }
+
+    /// Первый поддерживаемый язык из `Accept-Language` с учётом весов `q`.
+    ///
+    /// ## Пример
+    /// `ru-RU,ru;q=0.9,en;q=0.8` -> `Locale::Ru`
+    pub fn negotiate(accept_language: &str) -> Option<Locale> {
+        let mut candidates: Vec<(f32, Locale)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';').map(str::trim);
+                let tag = parts.next()?;
+                let q = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                let language = tag.split('-').next()?;
+                let locale = language.parse().ok()?;
+                (q > 0.0).then_some((q, locale))
+            })
+            .collect();
+        // Сортировка устойчивая, при равных весах остаётся порядок из заголовка
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates.first().map(|(_, locale)| *locale)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| "expected 'en' or 'ru'".to_string())
+    }
+}
+
+/// Каталог одного языка: точные сообщения и шаблоны с `{}`.
+struct Catalog {
+    exact: HashMap<String, String>,
+    /// Части ключа между `{}` и перевод.
+    templates: Vec<(Vec<String>, String)>,
+}
+
+impl Catalog {
+    fn parse(source: &str) -> Self {
+        let messages: HashMap<String, String> =
+            toml::from_str(source).expect("Invalid message catalog");
+        let templates = messages
+            .iter()
+            .filter(|(key, _)| key.contains("{}"))
+            .map(|(key, value)| (key.split("{}").map(str::to_string).collect(), value.clone()))
+            .collect();
+        Catalog {
+            exact: messages,
+            templates,
+        }
+    }
+
+    fn translate(&self, message: &str) -> String {
+        if let Some(translation) = self.exact.get(message) {
+            return translation.clone();
+        }
+        self.templates
+            .iter()
+            .find_map(|(parts, translation)| {
+                matches(parts, message).map(|args| fill(translation, args.into_iter()))
+            })
+            .unwrap_or_else(|| message.to_string())
+    }
+}
+
+/// Значения на месте `{}`, если `message` получено из шаблона с частями `parts`.
+fn matches(parts: &[String], message: &str) -> Option<Vec<String>> {
+    let (first, rest) = parts.split_first()?;
+    let (last, middle) = rest.split_last()?;
+    let mut tail = message.strip_prefix(first.as_str())?;
+    let mut args = Vec::with_capacity(rest.len());
+    for part in middle {
+        let end = tail.find(part.as_str())?;
+        args.push(tail[..end].to_string());
+        tail = &tail[end + part.len()..];
+    }
+    args.push(tail.strip_suffix(last.as_str())?.to_string());
+    Some(args)
+}
+
+fn fill(template: &str, args: impl Iterator<Item = String>) -> String {
+    let mut parts = template.split("{}");
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(args.chain(std::iter::repeat(String::new()))) {
+        result.push_str(&arg);
+        result.push_str(part);
+    }
+    result
+}
+
+static CATALOGS: LazyLock<[Catalog; 2]> = LazyLock::new(|| {
+    [
+        Catalog::parse(include_str!("../locales/en.toml")),
+        Catalog::parse(include_str!("../locales/ru.toml")),
+    ]
+});
+
+static DEFAULT: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+tokio::task_local! {
+    static LOCALE: Cell<Locale>;
+}
+
+/// Язык для запросов без `Accept-Language` и вне запросов.
+pub fn configure(default: Locale) {
+    DEFAULT.store(default as u8, Ordering::Relaxed);
+}
+
+pub fn default_locale() -> Locale {
+    Locale::ALL[DEFAULT.load(Ordering::Relaxed) as usize]
+}
+
+/// Язык текущего запроса.
+pub fn current() -> Locale {
+    LOCALE
+        .try_with(Cell::get)
+        .unwrap_or_else(|_| default_locale())
+}
+
+/// Заменяет язык текущего запроса, например на выбранный сотрудником в профиле.
+pub fn prefer(locale: Locale) {
+    let _ = LOCALE.try_with(|current| current.set(locale));
+}
+
+/// Перевод на язык текущего запроса.
+pub fn translate(message: &str) -> String {
+    current().translate(message)
+}
+
+/// Middleware: выбирает язык по `Accept-Language` на время обработки запроса
+/// и сообщает его в `Content-Language`.
+pub async fn negotiate(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Locale::negotiate)
+        .unwrap_or_else(default_locale);
+    let (mut response, locale) = LOCALE
+        .scope(Cell::new(locale), async {
+            let response = next.run(request).await;
+            (response, current())
+        })
+        .await;
+    response.headers_mut().insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(locale.code()),
+    );
+    response
+}
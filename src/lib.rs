@@ -1,31 +1,198 @@
-mod config;
+pub mod admin;
+pub mod cli;
+pub mod config;
 mod db;
 pub mod features;
+pub mod i18n;
 pub mod logger;
+pub mod mail;
+pub mod middleware;
 pub mod models;
+pub mod openapi;
+pub mod password;
+pub mod rate_limit;
+mod shutdown;
+pub mod telemetry;
 
-use axum::Router;
+use axum::{Extension, Router};
+use tokio_util::sync::CancellationToken;
 
+use crate::cli::MigrateAction;
 use crate::config::Config;
+use crate::rate_limit::RateLimiter;
 use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
 
 pub async fn server_run() -> Result<(), Box<dyn Error>> {
     let config = Config::build()?;
-    match config.profile.as_str() {
-        "prod" => logger::init_prod_logger(),
-        _ => logger::init_dev_logger(),
+    let tracer_provider = logger::tracer_provider(&config.telemetry)?;
+    logger::init(config.profile, tracer_provider.as_ref());
+    password::configure(&config.security)?;
+    i18n::configure(config.http.default_locale);
+    if let Some(endpoint) = &config.telemetry.otlp_endpoint {
+        tracing::info!("Exporting traces to {endpoint}");
     }
 
     tracing::info!("Starting application");
 
-    let pool = db::connect(&config.db_url)?;
-    let service = features::services::new(&pool);
-    let app = Router::new().merge(service);
+    let pool = db::connect(&config.database)?;
+    if config.database.migrate_on_start {
+        db::migrate_up(&pool).await?;
+    }
+
+    // Списки и отчёты читаются с реплики, если она задана
+    let replica = db::connect_read(&config.database)?;
+    let read_pool = replica.clone().unwrap_or_else(|| pool.clone());
+
+    let keys = match &config.security.jwt_secret {
+        Some(secret) => features::auth::Keys::new(secret),
+        None => {
+            tracing::warn!("JWT_SECRET is not set, tokens will be invalidated on restart");
+            features::auth::Keys::random()
+        }
+    };
+    let keys = Arc::new(match &config.security.jwt_refresh_secret {
+        Some(secret) => keys.with_refresh_secret(secret),
+        None => keys,
+    });
+    let authenticator = Arc::new(
+        features::auth::Authenticator::new(&pool, keys.clone())
+            .with_required_2fa(config.security.require_2fa),
+    );
+    let ip_limiter = Arc::new(RateLimiter::from_config(
+        &config.rate_limit,
+        config.rate_limit.per_ip,
+        &pool,
+    ));
+    let account_limiter = Arc::new(RateLimiter::from_config(
+        &config.rate_limit,
+        config.rate_limit.per_account,
+        &pool,
+    ));
+
+    let mailer = mail::from_config(&config.mail)?;
+    let auth = features::auth::new(
+        &pool,
+        features::auth::Settings::new(keys.clone())
+            .with_limiter(account_limiter)
+            .with_lockout_threshold(config.security.lockout_threshold)
+            .with_mailer(mailer)
+            .with_refresh_ttl(config.security.refresh_token_ttl)
+            .with_password_reset(
+                config.security.password_reset_ttl,
+                config.mail.password_reset_url.clone(),
+            )
+            .with_two_factor(&config.security.totp_issuer, config.security.require_2fa),
+    );
+    let api_keys = features::api_keys::new(&pool);
+    let service = features::services::with_read_pool(&pool, &read_pool);
+    let categories = features::categories::new(&pool);
+    let employee = features::employee::with_read_pool(&pool, &read_pool);
+    let webhooks = features::webhooks::new(&pool);
+    let requests = features::requests::new(&pool);
+    let reports = features::reports::new(&read_pool);
+    let export = features::export::new(&read_pool);
+    let import = features::import::new(&pool);
+    let health = features::health::new(&pool);
+    let metrics = features::metrics::with_read_pool(&pool, replica.as_ref());
+    let app = Router::new()
+        .merge(auth)
+        .merge(api_keys)
+        .merge(service)
+        .merge(categories)
+        .merge(employee)
+        .merge(webhooks)
+        .merge(requests)
+        .merge(reports)
+        .merge(export)
+        .merge(import)
+        .merge(health)
+        .merge(metrics)
+        .merge(openapi::new(config.features.swagger_ui));
+    let app = rate_limit::protect(app, ip_limiter, config.rate_limit.trust_forwarded_for);
+    let app = middleware::apply(app, &config.http).layer(Extension(authenticator));
+
+    tracing::info!("Server running on {}", config.server.addr);
+    let listener = tokio::net::TcpListener::bind(config.server.addr).await?;
+    let stop = CancellationToken::new();
+    // Адрес соединения нужен для ограничения частоты запросов по IP
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+    let serve = axum::serve(listener, app).with_graceful_shutdown(stop.clone().cancelled_owned());
+    let mut server = tokio::spawn(async move { serve.await });
 
-    tracing::info!("Server running on {}:{}", config.ip, config.port);
-    let listener = tokio::net::TcpListener::bind(format!("{}:{}", config.ip, config.port))
+    let signal = tokio::select! {
+        signal = shutdown::signal() => signal,
+        result = &mut server => return Ok(result??),
+    };
+
+    // Новые соединения больше не принимаются, начатые запросы дорабатывают
+    // в пределах `shutdown_timeout`, оставшееся время получает фоновая рассылка.
+    tracing::info!("Received {signal}, shutting down");
+    let started = Instant::now();
+    stop.cancel();
+    let requests_drained = tokio::time::timeout(config.server.shutdown_timeout, &mut server)
         .await
-        .unwrap();
-    axum::serve(listener, app).await.unwrap();
+        .is_ok();
+    if !requests_drained {
+        tracing::warn!(
+            "In-flight requests did not finish within {:?}",
+            config.server.shutdown_timeout
+        );
+        server.abort();
+    }
+
+    let remaining = config
+        .server
+        .shutdown_timeout
+        .saturating_sub(started.elapsed());
+    let unfinished_tasks = features::webhooks::dispatcher::stop(remaining).await;
+    pool.close().await;
+    read_pool.close().await;
+
+    tracing::info!(
+        signal,
+        requests_drained,
+        unfinished_tasks,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "Shutdown complete"
+    );
+
+    // Досылаем последнюю пачку span'ов, экспорт блокирует поток
+    if let Some(provider) = tracer_provider
+        && let Err(err) = tokio::task::spawn_blocking(move || provider.shutdown()).await?
+    {
+        tracing::error!("Failed to flush traces: {err}");
+    }
+    Ok(())
+}
+
+pub async fn migrate_run(action: MigrateAction) -> Result<(), Box<dyn Error>> {
+    let config = Config::build()?;
+    logger::init_dev_logger();
+
+    let pool = db::connect(&config.database)?;
+    match action {
+        MigrateAction::Up => db::migrate_up(&pool).await?,
+        MigrateAction::Down { steps } => db::migrate_down(&pool, steps).await?,
+        MigrateAction::Status => {
+            let applied = db::migrate_applied(&pool).await?;
+            for migration in db::MIGRATOR.iter() {
+                if !migration.migration_type.is_up_migration() {
+                    continue;
+                }
+                let status = match applied.iter().find(|m| m.version == migration.version) {
+                    Some(m) if m.checksum != migration.checksum => "changed",
+                    Some(_) => "applied",
+                    None => "pending",
+                };
+                println!(
+                    "{} {:<8} {}",
+                    migration.version, status, migration.description
+                );
+            }
+        }
+    }
     Ok(())
 }
@@ -1,9 +1,19 @@
+use std::error::Error;
+
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Subscriber;
 use tracing_subscriber::{
-    EnvFilter,
+    EnvFilter, Layer,
     fmt::{self},
     prelude::*,
+    registry::LookupSpan,
 };
 
+use crate::config::{Profile, TelemetryConfig};
+
 // Уровень логгера задаётся из .env с поля RUST_LOG.
 // Возможные значения:
 // RUST_LOG=info: Только info, warn, error;
@@ -18,29 +28,62 @@ use tracing_subscriber::{
 // tracing::trace!: Очень подробные логи.
 
 pub fn init_dev_logger() {
-    // Настройка формата логов: время, уровень, модуль
-    let fmt_layer = fmt::layer().with_line_number(true);
+    init(Profile::Dev, None);
+}
+
+pub fn init_prod_logger() {
+    init(Profile::Prod, None);
+}
+
+/// Устанавливает глобальный subscriber: формат логов по профилю и, если передан провайдер,
+/// слой экспорта span'ов в OpenTelemetry.
+pub fn init(profile: Profile, tracer_provider: Option<&SdkTracerProvider>) {
+    // Настройка формата логов: время, уровень, модуль. JSON-формат для продакшена
+    let fmt_layer = match profile {
+        Profile::Dev => fmt::layer().with_line_number(true).boxed(),
+        Profile::Prod => fmt::layer().json().with_line_number(true).boxed(),
+    };
 
     // Читаем уровень логов из переменной окружения RUST_LOG (например, "info", "debug")
     let filter_layer = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
-    // Устанавливаем глобальный subscriber
-    tracing_subscriber::registry()
+    // Устанавливаем глобальный subscriber (повторный вызов, например в тестах, игнорируется)
+    let _ = tracing_subscriber::registry()
         .with(filter_layer)
         .with(fmt_layer)
-        .init();
+        .with(tracer_provider.map(otel_layer))
+        .try_init();
 }
 
-pub fn init_prod_logger() {
-    // JSON-формат для продакшена
-    let fmt_layer = fmt::layer()
-        .json() // Вывод в JSON
-        .with_line_number(true);
-
-    let filter_layer = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+/// Провайдер трассировок с экспортом по OTLP/HTTP, `None` если коллектор не настроен.
+///
+/// Span'ы отправляются пачками из отдельного потока, перед выходом нужно вызвать
+/// `shutdown`, чтобы дослать последнюю пачку.
+pub fn tracer_provider(
+    config: &TelemetryConfig,
+) -> Result<Option<SdkTracerProvider>, Box<dyn Error>> {
+    let Some(endpoint) = &config.otlp_endpoint else {
+        return Ok(None);
+    };
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .with_batch_exporter(exporter)
+        .build();
+    Ok(Some(provider))
+}
 
-    tracing_subscriber::registry()
-        .with(filter_layer)
-        .with(fmt_layer)
-        .init();
+/// Слой, превращающий span'ы `tracing` в span'ы OpenTelemetry.
+pub fn otel_layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + use<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
 }
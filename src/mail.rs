@@ -0,0 +1,98 @@
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::BoxFuture;
+use lettre::message::Mailbox;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::{MailConfig, MailTransport};
+
+/// Письмо в виде простого текста.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Способ доставки писем. Реализации выбираются в `from_config`, тесты подставляют `MemoryMailer`.
+pub trait Mailer: Send + Sync {
+    fn send(&self, mail: Mail) -> BoxFuture<'_, Result<(), String>>;
+}
+
+pub fn from_config(config: &MailConfig) -> Result<Arc<dyn Mailer>, String> {
+    match config.transport {
+        MailTransport::Log => Ok(Arc::new(LogMailer)),
+        MailTransport::Smtp => {
+            let url = config.smtp_url.as_deref().unwrap_or_default();
+            Ok(Arc::new(SmtpMailer::new(url, &config.from)?))
+        }
+    }
+}
+
+/// Пишет письма в лог вместо отправки, для разработки.
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+    fn send(&self, mail: Mail) -> BoxFuture<'_, Result<(), String>> {
+        Box::pin(async move {
+            tracing::info!(to = mail.to, subject = mail.subject, "Mail:\n{}", mail.body);
+            Ok(())
+        })
+    }
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// `url` в формате `smtp[s]://user:password@host:port`.
+    pub fn new(url: &str, from: &str) -> Result<Self, String> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+            .map_err(|err| format!("invalid SMTP url: {err}"))?
+            .build();
+        let from = from
+            .parse()
+            .map_err(|err| format!("invalid sender address: {err}"))?;
+        Ok(SmtpMailer { transport, from })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, mail: Mail) -> BoxFuture<'_, Result<(), String>> {
+        Box::pin(async move {
+            let to: Mailbox = mail.to.parse().map_err(|err| format!("{err}"))?;
+            let message = Message::builder()
+                .from(self.from.clone())
+                .to(to)
+                .subject(mail.subject)
+                .body(mail.body)
+                .map_err(|err| err.to_string())?;
+            self.transport
+                .send(message)
+                .await
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        })
+    }
+}
+
+/// Складывает письма в память, чтобы их можно было прочитать в тестах.
+#[derive(Default, Clone)]
+pub struct MemoryMailer {
+    sent: Arc<Mutex<Vec<Mail>>>,
+}
+
+impl MemoryMailer {
+    pub fn sent(&self) -> Vec<Mail> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl Mailer for MemoryMailer {
+    fn send(&self, mail: Mail) -> BoxFuture<'_, Result<(), String>> {
+        self.sent.lock().unwrap().push(mail);
+        Box::pin(async { Ok(()) })
+    }
+}
@@ -1,8 +1,18 @@
 use std::process;
 
+use clap::Parser;
+use mds_backend_rust::cli::{Cli, Command};
+
 #[tokio::main]
 async fn main() {
-    if let Err(e) = mds_backend_rust::server_run().await {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        None | Some(Command::Serve) => mds_backend_rust::server_run().await,
+        Some(Command::Migrate { action }) => mds_backend_rust::migrate_run(action).await,
+        Some(command) => mds_backend_rust::admin::run(command).await,
+    };
+
+    if let Err(e) = result {
         eprintln!("Application error: {e}");
         process::exit(1);
     }
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode};
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tower::ServiceBuilder;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::timeout::TimeoutLayer;
+use tower_http::trace::TraceLayer;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::config::HttpConfig;
+use crate::{i18n, telemetry};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Оборачивает роутер общими слоями. Порядок сверху вниз - от внешнего к внутреннему:
+/// id запроса выставляется раньше всех, чтобы попасть в span и в ответ даже при таймауте.
+pub fn apply(router: Router, config: &HttpConfig) -> Router {
+    let request_id = HeaderName::from_static(REQUEST_ID_HEADER);
+
+    let router = router
+        // Язык сообщений об ошибках, см. `i18n::negotiate`
+        .layer(axum::middleware::from_fn(i18n::negotiate))
+        // Ограничение `Json`/`String` экстракторов axum заменяется общим лимитом ниже
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(config.body_limit));
+
+    let router = if config.compression {
+        router.layer(CompressionLayer::new().gzip(true).br(true))
+    } else {
+        router
+    };
+
+    router
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::new(request_id.clone(), MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(make_span)
+                        .on_request(())
+                        .on_response(on_response),
+                )
+                .layer(PropagateRequestIdLayer::new(request_id))
+                .layer(cors(&config.cors_allowed_origins))
+                .layer(TimeoutLayer::with_status_code(
+                    StatusCode::REQUEST_TIMEOUT,
+                    config.request_timeout,
+                )),
+        )
+        // Слои роутера оборачивают каждый маршрут уже после сопоставления пути,
+        // поэтому шаблон маршрута доступен и в самом внешнем слое
+        .layer(axum::middleware::from_fn(telemetry::track_http))
+}
+
+fn cors(origins: &[String]) -> CorsLayer {
+    // Без списка источников заголовки CORS не выставляются, и браузер отклоняет кросс-доменные запросы
+    let origins: Vec<HeaderValue> = origins.iter().filter_map(|o| o.parse().ok()).collect();
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)])
+}
+
+fn make_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    // Продолжаем трассировку клиента или шлюза из W3C `traceparent`. Без заголовка, как и без
+    // настроенного экспорта, span остаётся корневым
+    let parent = TraceContextPropagator::new().extract(&Headers(request.headers()));
+    let _ = span.set_parent(parent);
+    span
+}
+
+struct Headers<'a>(&'a HeaderMap);
+
+impl Extractor for Headers<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+fn on_response<B>(response: &Response<B>, latency: Duration, _span: &Span) {
+    tracing::info!(
+        status = response.status().as_u16(),
+        latency_ms = latency.as_millis() as u64,
+        "Request completed"
+    );
+}
@@ -1,11 +1,22 @@
+use crate::i18n::{self, Locale};
 use crate::models::dto;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::{Decode, Encode, Type, encode::IsNull, error::BoxDynError, postgres::PgTypeInfo};
+use utoipa::ToSchema;
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct Service {
     id: Option<i64>,
     name: String,
+    category_id: Option<i64>,
+    description: Option<String>,
+    /// В минимальных единицах валюты, см. `dto::Service::base_price`.
+    base_price: Option<i64>,
+    currency: Option<String>,
+    duration_minutes: Option<i32>,
+    skill_level: Option<String>,
+    active: bool,
     created_at: DateTime<Utc>,
     updated_at: Option<DateTime<Utc>>,
 }
@@ -15,14 +26,73 @@ impl Service {
         dto::Service {
             id: from.id,
             name: from.name,
+            category_id: Some(from.category_id),
+            description: Some(from.description),
+            base_price: Some(from.base_price),
+            currency: Some(from.currency),
+            duration_minutes: Some(from.duration_minutes),
+            skill_level: Some(from.skill_level),
+            active: Some(from.active),
             created_at: Some(from.created_at),
             updated_at: from.updated_at,
         }
     }
 }
 
+/// Уровень квалификации исполнителя, нужный для услуги. Хранится кодом.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(i16)]
+pub enum SkillLevel {
+    Junior,
+    Middle,
+    Senior,
+}
+
+impl SkillLevel {
+    pub const ALL: [SkillLevel; 3] = [SkillLevel::Junior, SkillLevel::Middle, SkillLevel::Senior];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            SkillLevel::Junior => "junior",
+            SkillLevel::Middle => "middle",
+            SkillLevel::Senior => "senior",
+        }
+    }
+
+    pub fn from(str: &str) -> Result<SkillLevel, dto::Error> {
+        SkillLevel::ALL
+            .into_iter()
+            .find(|level| level.code() == str)
+            .ok_or_else(|| dto::Error::BadRequest(format!("Unknown skill level: {}", str)))
+    }
+}
+
+/// Категория каталога услуг, корневая при пустом `parent_id`.
+#[derive(Debug, sqlx::FromRow)]
+pub struct Category {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Category {
+    pub fn to_dto(from: Category) -> dto::Category {
+        dto::Category {
+            id: Some(from.id),
+            name: from.name,
+            parent_id: from.parent_id,
+            created_at: Some(from.created_at),
+            updated_at: from.updated_at,
+        }
+    }
+}
+
+/// Роль сотрудника. В API передаётся кодом (`employee`, `manager`, `superadmin`),
+/// в базе хранится числом, переведённые названия отдаёт `/employee/roles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[repr(i32)]
 pub enum Role {
     Employee = 0,
     Manager = 1,
@@ -30,28 +100,58 @@ pub enum Role {
 }
 
 impl Role {
-    pub fn to_dto(&self) -> String {
+    pub const ALL: [Role; 3] = [Role::Employee, Role::Manager, Role::Superadmin];
+
+    /// Роли, которые управляют услугами и сотрудниками.
+    pub fn is_privileged(&self) -> bool {
+        matches!(self, Role::Manager | Role::Superadmin)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Role::Employee => "employee",
+            Role::Manager => "manager",
+            Role::Superadmin => "superadmin",
+        }
+    }
+
+    /// Роль по коду. Русские названия, которые API принимало раньше, тоже подходят.
+    pub fn from(str: &str) -> Result<Role, dto::Error> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.code() == str || Locale::Ru.translate(role.name()) == str)
+            .ok_or_else(|| dto::Error::BadRequest(format!("Unknown role: {}", str)))
+    }
+
+    /// Название для интерфейса, ключ каталога `locales`.
+    pub fn name(&self) -> &'static str {
         match self {
-            Role::Employee => String::from("Сотрудник"),
-            Role::Manager => String::from("Менеджер"),
-            Role::Superadmin => String::from("Суперадмин"),
+            Role::Employee => "Employee",
+            Role::Manager => "Manager",
+            Role::Superadmin => "Superadmin",
         }
     }
 
-    pub fn from(str: Option<String>) -> Result<Role, dto::Error> {
-        match str.as_deref() {
-            Some("Сотрудник") => Ok(Role::Employee),
-            Some("Менеджер") => Ok(Role::Manager),
-            Some("Суперадмин") => Ok(Role::Superadmin),
-            Some(_) => Err(dto::Error::BadRequest(String::from("Unknown string role"))),
-            None => Err(dto::Error::BadRequest(String::from("Role string is None"))),
+    /// Код и название на языке текущего запроса.
+    pub fn to_dto(&self) -> dto::RoleName {
+        dto::RoleName {
+            code: *self,
+            name: i18n::translate(self.name()),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for Role {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Role::from(&code).map_err(|err| serde::de::Error::custom(err.message()))
+    }
+}
+
+// Колонка `employee.role` объявлена как INTEGER
 impl Type<sqlx::Postgres> for Role {
     fn type_info() -> PgTypeInfo {
-        <i16 as Type<sqlx::Postgres>>::type_info()
+        <i32 as Type<sqlx::Postgres>>::type_info()
     }
 }
 
@@ -60,7 +160,7 @@ impl<'q> Encode<'q, sqlx::Postgres> for Role {
         &self,
         buf: &mut sqlx::postgres::PgArgumentBuffer,
     ) -> Result<IsNull, BoxDynError> {
-        <i16 as Encode<'q, sqlx::Postgres>>::encode(*self as i16, buf)
+        <i32 as Encode<'q, sqlx::Postgres>>::encode(*self as i32, buf)
     }
 }
 
@@ -68,13 +168,21 @@ impl<'r> Decode<'r, sqlx::Postgres> for Role {
     fn decode(
         value: sqlx::postgres::PgValueRef<'r>,
     ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
-        let id: i16 = <i16 as Decode<'r, sqlx::Postgres>>::decode(value)?;
-        Ok(match id {
-            0 => Role::Employee,
-            1 => Role::Manager,
-            2 => Role::Superadmin,
-            _ => return Err("Invalid role value".into()),
-        })
+        let id: i32 = <i32 as Decode<'r, sqlx::Postgres>>::decode(value)?;
+        Ok(Role::try_from(id)?)
+    }
+}
+
+impl TryFrom<i32> for Role {
+    type Error = &'static str;
+
+    fn try_from(id: i32) -> Result<Self, Self::Error> {
+        match id {
+            0 => Ok(Role::Employee),
+            1 => Ok(Role::Manager),
+            2 => Ok(Role::Superadmin),
+            _ => Err("Invalid role value"),
+        }
     }
 }
 
@@ -101,7 +209,7 @@ impl Employee {
             middle_name: from.middle_name,
             email: Some(from.email),
             password: Some(from.password),
-            role: Some(from.role.to_dto()),
+            role: Some(from.role.code().to_string()),
             services: None,
             // services: Some(
             //     from.services
@@ -115,3 +223,346 @@ impl Employee {
         }
     }
 }
+
+/// Данные сотрудника, нужные для проверки входа.
+#[derive(Debug, sqlx::FromRow)]
+pub struct Credentials {
+    pub id: i64,
+    pub password: String,
+    pub role: Role,
+    pub active: bool,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub totp_enabled: bool,
+    /// Язык сообщений, выбранный сотрудником.
+    pub locale: Option<String>,
+}
+
+/// Владелец сессии, данные для проверки доступа по access токену.
+#[derive(Debug, sqlx::FromRow)]
+pub struct SessionOwner {
+    pub role: Role,
+    pub totp_enabled: bool,
+    pub locale: Option<String>,
+}
+
+/// Состояние двухфакторной аутентификации сотрудника.
+#[derive(Debug, sqlx::FromRow)]
+pub struct Totp {
+    pub email: String,
+    pub secret: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Session {
+    pub id: i64,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn to_dto(self, current: i64) -> dto::Session {
+        dto::Session {
+            current: self.id == current,
+            id: self.id,
+            user_agent: self.user_agent,
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    ServiceCreated,
+    ServiceUpdated,
+    ServiceDeleted,
+    RequestCreated,
+}
+
+impl WebhookEvent {
+    /// События, которые приложение рассылает. Из событий заявок пока есть только
+    /// создание: изменять и закрывать заявки через API нельзя.
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::ServiceCreated,
+        WebhookEvent::ServiceUpdated,
+        WebhookEvent::ServiceDeleted,
+        WebhookEvent::RequestCreated,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            WebhookEvent::ServiceCreated => "service.created",
+            WebhookEvent::ServiceUpdated => "service.updated",
+            WebhookEvent::ServiceDeleted => "service.deleted",
+            WebhookEvent::RequestCreated => "request.created",
+        }
+    }
+
+    pub fn from(str: &str) -> Result<WebhookEvent, dto::Error> {
+        WebhookEvent::ALL
+            .into_iter()
+            .find(|event| event.code() == str)
+            .ok_or_else(|| dto::Error::BadRequest(format!("Unknown webhook event: {}", str)))
+    }
+}
+
+/// Права, которые выдаются API ключам.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    RequestsCreate,
+    /// Отчёты и выгрузки.
+    ReportsRead,
+    /// Подписки на вебхуки.
+    WebhooksManage,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 3] = [
+        Permission::RequestsCreate,
+        Permission::ReportsRead,
+        Permission::WebhooksManage,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Permission::RequestsCreate => "requests:create",
+            Permission::ReportsRead => "reports:read",
+            Permission::WebhooksManage => "webhooks:manage",
+        }
+    }
+
+    pub fn from(str: &str) -> Result<Permission, dto::Error> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.code() == str)
+            .ok_or_else(|| dto::Error::BadRequest(format!("Unknown permission: {}", str)))
+    }
+
+    /// Роли сотрудников, которым право принадлежит без API ключа.
+    pub fn roles(&self) -> &'static [Role] {
+        match self {
+            Permission::RequestsCreate => &[Role::Employee, Role::Manager, Role::Superadmin],
+            Permission::ReportsRead => &[Role::Manager, Role::Superadmin],
+            Permission::WebhooksManage => &[Role::Manager, Role::Superadmin],
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    pub prefix: String,
+    pub permissions: Vec<String>,
+    pub created_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn to_dto(from: ApiKey) -> dto::ApiKey {
+        dto::ApiKey {
+            id: Some(from.id),
+            name: Some(from.name),
+            key: None,
+            prefix: Some(from.prefix),
+            permissions: Some(from.permissions),
+            created_by: from.created_by,
+            created_at: Some(from.created_at),
+            expires_at: from.expires_at,
+            last_used_at: from.last_used_at,
+            revoked_at: from.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Webhook {
+    pub fn to_dto(from: Webhook) -> dto::Webhook {
+        dto::Webhook {
+            id: Some(from.id),
+            url: Some(from.url),
+            // Секрет используется только для подписи и наружу не отдаётся
+            secret: None,
+            events: Some(from.events),
+            active: Some(from.active),
+            created_at: Some(from.created_at),
+            updated_at: from.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub payload: String,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn to_dto(from: WebhookDelivery) -> dto::WebhookDelivery {
+        dto::WebhookDelivery {
+            id: from.id,
+            webhook_id: from.webhook_id,
+            event: from.event,
+            payload: from.payload,
+            attempt: from.attempt,
+            status_code: from.status_code,
+            success: from.success,
+            error: from.error,
+            created_at: from.created_at,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct DailyVolume {
+    pub day: NaiveDate,
+    pub opened: i64,
+    pub closed: i64,
+}
+
+impl DailyVolume {
+    pub fn to_dto(from: DailyVolume) -> dto::DailyVolume {
+        dto::DailyVolume {
+            day: from.day,
+            opened: from.opened,
+            closed: from.closed,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ResolutionTime {
+    pub service_id: Option<i64>,
+    pub service_name: Option<String>,
+    pub closed: i64,
+    pub median_seconds: f64,
+    pub p90_seconds: f64,
+}
+
+impl ResolutionTime {
+    pub fn to_dto(from: ResolutionTime) -> dto::ResolutionTime {
+        dto::ResolutionTime {
+            service_id: from.service_id,
+            service_name: from.service_name,
+            closed: from.closed,
+            median_seconds: from.median_seconds,
+            p90_seconds: from.p90_seconds,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct EmployeeWorkload {
+    pub employee_id: i64,
+    pub name: String,
+    pub last_name: String,
+    pub open_requests: i64,
+}
+
+impl EmployeeWorkload {
+    pub fn to_dto(from: EmployeeWorkload) -> dto::EmployeeWorkload {
+        dto::EmployeeWorkload {
+            employee_id: from.employee_id,
+            name: from.name,
+            last_name: from.last_name,
+            open_requests: from.open_requests,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SlaCompliance {
+    pub met: i64,
+    pub breached: i64,
+    pub pending: i64,
+}
+
+impl SlaCompliance {
+    pub fn to_dto(from: SlaCompliance) -> dto::SlaCompliance {
+        let decided = from.met + from.breached;
+        dto::SlaCompliance {
+            met: from.met,
+            breached: from.breached,
+            pending: from.pending,
+            compliance_rate: (decided > 0).then(|| from.met as f64 / decided as f64),
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Request {
+    pub id: i64,
+    pub name: String,
+    pub service_id: Option<i64>,
+    pub owner_id: i64,
+    pub priority: i16,
+    pub description: String,
+    pub status: i16,
+    pub desired_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl Request {
+    pub fn to_dto(from: Request) -> dto::Request {
+        dto::Request {
+            id: Some(from.id),
+            name: from.name,
+            service_id: from.service_id,
+            owner_id: Some(from.owner_id),
+            priority: Some(from.priority),
+            description: Some(from.description),
+            status: Some(from.status),
+            desired_at: Some(from.desired_at),
+            created_at: Some(from.created_at),
+            closed_at: from.closed_at,
+        }
+    }
+}
+
+/// Строка выгрузки заявок с подставленными названием услуги и ФИО.
+#[derive(Debug, sqlx::FromRow)]
+pub struct RequestExport {
+    pub id: i64,
+    pub name: String,
+    pub service_name: Option<String>,
+    pub owner_name: String,
+    pub assignee_name: Option<String>,
+    pub priority: i16,
+    pub status: i16,
+    pub created_at: DateTime<Utc>,
+    pub desired_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Количество открытых заявок по статусу и приоритету.
+#[derive(Debug, sqlx::FromRow)]
+pub struct OpenRequests {
+    pub status: i16,
+    pub priority: i16,
+    pub count: i64,
+}
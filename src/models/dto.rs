@@ -1,28 +1,157 @@
+use std::time::Duration;
+
 use axum::{Json, http::StatusCode};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::types::chrono;
+use utoipa::{IntoParams, ToSchema};
+
+pub use super::dao::Role;
+use crate::i18n;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Service {
     pub id: Option<i64>,
     pub name: String,
+    /// Категория услуги, `null` - без категории. При изменении без поля
+    /// услуга остаётся в прежней категории.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i64>)]
+    pub category_id: Option<Option<i64>>,
+    /// Описание услуги.
+    ///
+    /// Здесь и в полях ниже при изменении отсутствующее поле остаётся прежним,
+    /// а `null` очищает значение. Цена и валюта очищаются вместе.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    /// Базовая цена в минимальных единицах валюты (копейках, центах), а не в рублях:
+    /// `1500000` с валютой `RUB` - 15 000 рублей.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i64>, example = 1500000)]
+    pub base_price: Option<Option<i64>>,
+    /// Код валюты цены по ISO 4217, например `RUB`.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub currency: Option<Option<String>>,
+    /// Ориентировочная длительность работ в минутах.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i32>)]
+    pub duration_minutes: Option<Option<i32>>,
+    /// Нужный уровень исполнителя: `junior`, `middle` или `senior`.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub skill_level: Option<Option<String>>,
+    /// Принимаются ли новые заявки по услуге. При создании по умолчанию `true`,
+    /// при изменении без поля остаётся прежним.
+    #[serde(default)]
+    pub active: Option<bool>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Отличает отсутствующее поле (`None`, через `#[serde(default)]`) от явного `null`
+/// (`Some(None)`).
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 impl Service {
     pub fn new(id: Option<i64>, name: Option<String>) -> Self {
         Service {
-            id: id,
-            name: name.unwrap_or_else(|| "".to_string()),
+            id,
+            name: name.unwrap_or_default(),
+            category_id: None,
+            description: None,
+            base_price: None,
+            currency: None,
+            duration_minutes: None,
+            skill_level: None,
+            active: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+/// Фильтр списка услуг.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ServiceFilter {
+    /// Услуги категории вместе с её подкатегориями.
+    pub category_id: Option<i64>,
+    /// Только активные или только неактивные услуги.
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Category {
+    pub id: Option<i64>,
+    pub name: String,
+    /// Родительская категория, `null` - корневая.
+    #[serde(default)]
+    pub parent_id: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Category {
+    pub fn new(name: &str, parent_id: Option<i64>) -> Self {
+        Category {
+            id: None,
+            name: name.to_string(),
+            parent_id,
             created_at: None,
             updated_at: None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Узел дерева каталога: категория с подкатегориями и услугами.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CategoryNode {
+    pub id: i64,
+    pub name: String,
+    #[schema(no_recursion)]
+    pub children: Vec<CategoryNode>,
+    pub services: Vec<Service>,
+}
+
+/// Заявка. При создании задаются `name`, `desired_at` и при желании услуга, приоритет
+/// и описание, остальное заполняет сервер.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Request {
+    pub id: Option<i64>,
+    #[schema(example = "Сайт недоступен")]
+    pub name: String,
+    /// Услуга заявки. Новые заявки принимаются только по активным услугам.
+    #[serde(default)]
+    pub service_id: Option<i64>,
+    /// Автор. Заявка по API ключу записывается на сотрудника, выпустившего ключ.
+    #[serde(default)]
+    pub owner_id: Option<i64>,
+    /// Приоритет, при создании по умолчанию 0.
+    #[serde(default)]
+    pub priority: Option<i16>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Статус, у новой заявки 0.
+    #[serde(default)]
+    pub status: Option<i16>,
+    /// Желаемый срок выполнения.
+    #[serde(default)]
+    pub desired_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
     pub timestamp: DateTime<Utc>,
@@ -42,6 +171,11 @@ pub enum Error {
     Conflict(String),
     BadRequest(String),
     NotFound(String),
+    Unauthorized(String),
+    Forbidden(String),
+    Locked(String),
+    /// Лимит запросов исчерпан, второе поле - через сколько можно повторить.
+    TooManyRequests(String, Duration),
     InternalServerError(String),
 }
 
@@ -51,25 +185,37 @@ impl Error {
             Error::Conflict(_) => StatusCode::CONFLICT,
             Error::BadRequest(_) => StatusCode::BAD_REQUEST,
             Error::NotFound(_) => StatusCode::NOT_FOUND,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::Forbidden(_) => StatusCode::FORBIDDEN,
+            Error::Locked(_) => StatusCode::LOCKED,
+            Error::TooManyRequests(..) => StatusCode::TOO_MANY_REQUESTS,
             Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
-    pub fn into_response(self) -> (StatusCode, Json<ErrorResponse>) {
-        let message = match &self {
+    pub fn message(&self) -> &str {
+        match self {
             Error::Conflict(msg) => msg,
             Error::BadRequest(msg) => msg,
             Error::NotFound(msg) => msg,
+            Error::Unauthorized(msg) => msg,
+            Error::Forbidden(msg) => msg,
+            Error::Locked(msg) => msg,
+            Error::TooManyRequests(msg, _) => msg,
             Error::InternalServerError(msg) => msg,
-        };
+        }
+    }
+
+    /// Ответ с сообщением на языке текущего запроса, см. `i18n::negotiate`.
+    pub fn into_response(self) -> (StatusCode, Json<ErrorResponse>) {
         (
             self.status_code(),
-            Json(ErrorResponse::new(message.to_string())),
+            Json(ErrorResponse::new(i18n::translate(self.message()))),
         )
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Employee {
     pub id: Option<i64>,
     pub name: Option<String>,
@@ -77,9 +223,297 @@ pub struct Employee {
     pub middle_name: Option<String>,
     pub email: Option<String>,
     pub password: Option<String>,
+    /// Код роли, см. `/employee/roles`. Строкой, чтобы неизвестный код проверял
+    /// `employee::logic` и отвечал переведённой ошибкой 400, а не отказом разбора тела.
+    #[schema(example = "manager")]
     pub role: Option<String>,
     pub services: Option<Vec<Service>>,
     pub active: Option<bool>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+/// Роль и её название для интерфейса.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RoleName {
+    pub code: Role,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Login {
+    pub email: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PasswordChange {
+    pub current_password: Option<String>,
+    pub new_password: Option<String>,
+}
+
+/// Запрос на восстановление пароля по email.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PasswordForgot {
+    pub email: Option<String>,
+}
+
+/// Новый пароль по токену из письма восстановления.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PasswordReset {
+    pub token: Option<String>,
+    pub new_password: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Token {
+    pub access_token: String,
+    /// Всегда `Bearer`.
+    pub token_type: String,
+    /// Время жизни access токена в секундах.
+    pub expires_in: u64,
+    /// Одноразовый токен для `/auth/refresh`, при обновлении выдаётся новый.
+    pub refresh_token: String,
+}
+
+/// Ответ на вход с правильным паролем, когда нужен ещё код 2FA.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MfaChallenge {
+    /// Передаётся в `/auth/login/2fa` вместе с кодом.
+    pub mfa_token: String,
+    /// Время жизни `mfa_token` в секундах.
+    pub expires_in: u64,
+}
+
+/// Второй шаг входа: код из приложения или один из кодов восстановления.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MfaLogin {
+    pub mfa_token: Option<String>,
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+/// Код из приложения-аутентификатора или код восстановления.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TotpCode {
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TotpSetup {
+    /// Секрет в base32 для ручного ввода.
+    pub secret: String,
+    /// Ссылка `otpauth://` для QR-кода.
+    pub provisioning_uri: String,
+}
+
+/// Одноразовые коды восстановления, показываются один раз.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Язык сообщений сотрудника, `null` - по `Accept-Language`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LocalePreference {
+    pub locale: Option<i18n::Locale>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Refresh {
+    pub refresh_token: Option<String>,
+}
+
+/// Активная сессия сотрудника.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Session {
+    pub id: i64,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Сессия, которой принадлежит access токен запроса.
+    pub current: bool,
+}
+
+/// API ключ интеграции. При создании задаются `name`, `permissions` и `expires_at`,
+/// сам ключ возвращается в `key` только в ответе на создание.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ApiKey {
+    pub id: Option<i64>,
+    pub name: Option<String>,
+    pub key: Option<String>,
+    /// Начало ключа, по которому его можно узнать в списке.
+    pub prefix: Option<String>,
+    /// Например `requests:create`.
+    pub permissions: Option<Vec<String>>,
+    pub created_by: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Webhook {
+    pub id: Option<i64>,
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    /// `service.created`, `service.updated`, `service.deleted` или `request.created`.
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub payload: String,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Фильтры отчётов: диапазон дат включительно и услуга.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub service_id: Option<i64>,
+}
+
+impl ReportFilter {
+    pub fn validate(&self) -> Result<(), Error> {
+        if let (Some(from), Some(to)) = (self.from, self.to)
+            && from > to
+        {
+            return Err(Error::BadRequest(
+                "Parameter 'from' can't be after 'to'.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DailyVolume {
+    pub day: NaiveDate,
+    pub opened: i64,
+    pub closed: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ResolutionTime {
+    pub service_id: Option<i64>,
+    pub service_name: Option<String>,
+    pub closed: i64,
+    pub median_seconds: f64,
+    pub p90_seconds: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EmployeeWorkload {
+    pub employee_id: i64,
+    pub name: String,
+    pub last_name: String,
+    pub open_requests: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SlaCompliance {
+    pub met: i64,
+    pub breached: i64,
+    pub pending: i64,
+    pub compliance_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImportRowError {
+    /// Номер строки в файле, считая заголовок первой строкой.
+    pub row: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub valid: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Liveness {
+    pub status: HealthStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DatabaseCheck {
+    pub status: HealthStatus,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MigrationsCheck {
+    pub status: HealthStatus,
+    /// Версии миграций, собранные в бинарник, но не применённые к базе.
+    pub pending: Vec<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PoolCheck {
+    /// `Down`, только если соединение из пула не получено. Занятость на статус не влияет.
+    pub status: HealthStatus,
+    pub size: u32,
+    pub idle: usize,
+    pub max_connections: u32,
+    /// Доля занятых соединений от `max_connections`.
+    pub saturation: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Readiness {
+    pub status: HealthStatus,
+    pub database: DatabaseCheck,
+    pub migrations: MigrationsCheck,
+    pub pool: PoolCheck,
+}
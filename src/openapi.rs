@@ -0,0 +1,36 @@
+use axum::{Json, Router, routing::get};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::features;
+
+#[derive(OpenApi)]
+#[openapi(info(title = "MDS backend", description = "API сервиса заявок"))]
+struct ApiDoc;
+
+/// Собирает документ OpenAPI из описаний маршрутов всех фич.
+pub fn document() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    doc.merge(features::auth::openapi::ApiDoc::openapi());
+    doc.merge(features::api_keys::openapi::ApiDoc::openapi());
+    doc.merge(features::services::openapi::ApiDoc::openapi());
+    doc.merge(features::categories::openapi::ApiDoc::openapi());
+    doc.merge(features::employee::openapi::ApiDoc::openapi());
+    doc.merge(features::webhooks::openapi::ApiDoc::openapi());
+    doc.merge(features::requests::openapi::ApiDoc::openapi());
+    doc.merge(features::reports::openapi::ApiDoc::openapi());
+    doc.merge(features::export::openapi::ApiDoc::openapi());
+    doc.merge(features::import::openapi::ApiDoc::openapi());
+    doc.merge(features::health::openapi::ApiDoc::openapi());
+    doc
+}
+
+/// Отдаёт документ по `/openapi.json`, а при `swagger` ещё и Swagger UI по `/swagger-ui`.
+pub fn new(swagger: bool) -> Router {
+    let doc = document();
+    if swagger {
+        Router::new().merge(SwaggerUi::new("/swagger-ui").url("/openapi.json", doc))
+    } else {
+        Router::new().route("/openapi.json", get(move || async move { Json(doc) }))
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Argon2, Params};
+use tokio::sync::{OnceCell, Semaphore};
+
+use crate::config::{PasswordAlgorithm, SecurityConfig};
+use crate::models::dto;
+
+/// Стоимость bcrypt, если `configure` не вызывался, например в тестах.
+const DEFAULT_BCRYPT_COST: u32 = 14;
+
+static HASHER: OnceLock<Hasher> = OnceLock::new();
+static DUMMY_HASH: OnceCell<String> = OnceCell::const_new();
+
+/// Хеширование паролей вне потоков Tokio.
+///
+/// bcrypt и Argon2 занимают ядро на сотни миллисекунд, поэтому работа уходит в
+/// `spawn_blocking`, а семафор не даёт волне входов занять весь пул блокирующих задач.
+struct Hasher {
+    algorithm: PasswordAlgorithm,
+    bcrypt_cost: u32,
+    permits: Semaphore,
+    /// Число разрешений `permits`.
+    concurrency: usize,
+    policy: Policy,
+}
+
+/// Требования к новым паролям. Существующие хеши не перепроверяются.
+struct Policy {
+    min_length: usize,
+    min_classes: usize,
+    /// Утёкшие пароли в нижнем регистре.
+    denylist: HashSet<String>,
+}
+
+/// Задаёт алгоритм, стоимость, число одновременных хеширований и политику паролей.
+/// Вызывается один раз при запуске, повторные вызовы игнорируются.
+///
+/// Ошибка - если не читается файл `password_denylist`.
+pub fn configure(config: &SecurityConfig) -> std::io::Result<()> {
+    let denylist = match &config.password_denylist {
+        Some(path) => load_denylist(path)?,
+        None => HashSet::new(),
+    };
+    let _ = HASHER.set(Hasher {
+        algorithm: config.password_algorithm,
+        bcrypt_cost: config.bcrypt_cost,
+        permits: Semaphore::new(config.hash_concurrency),
+        concurrency: config.hash_concurrency,
+        policy: Policy {
+            min_length: config.password_min_length,
+            min_classes: config.password_min_classes,
+            denylist,
+        },
+    });
+    Ok(())
+}
+
+fn load_denylist(path: &str) -> std::io::Result<HashSet<String>> {
+    let denylist: HashSet<String> = std::fs::read_to_string(path)?
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect();
+    tracing::info!("Loaded {} denylisted passwords from {path}", denylist.len());
+    Ok(denylist)
+}
+
+fn hasher() -> &'static Hasher {
+    HASHER.get_or_init(|| {
+        let concurrency = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Hasher {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            bcrypt_cost: DEFAULT_BCRYPT_COST,
+            permits: Semaphore::new(concurrency),
+            concurrency,
+            policy: Policy {
+                min_length: 8,
+                min_classes: 3,
+                denylist: HashSet::new(),
+            },
+        }
+    })
+}
+
+/// Сколько хеширований выполняется одновременно. Пакетной работе, например импорту,
+/// нет смысла ставить в очередь больше: лишние задачи только задержат входы.
+pub fn concurrency() -> usize {
+    hasher().concurrency
+}
+
+/// Проверяет новый пароль на соответствие политике: длина, классы символов
+/// (строчные, заглавные, цифры, прочие) и отсутствие в списке утёкших.
+pub fn check_policy(password: &str) -> Result<(), dto::Error> {
+    let policy = &hasher().policy;
+    if password.chars().count() < policy.min_length {
+        return Err(dto::Error::BadRequest(format!(
+            "Password must be at least {} characters long.",
+            policy.min_length
+        )));
+    }
+
+    let classes = [
+        password.chars().any(char::is_lowercase),
+        password.chars().any(char::is_uppercase),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    if classes.iter().filter(|present| **present).count() < policy.min_classes {
+        return Err(dto::Error::BadRequest(format!(
+            "Password must contain at least {} of: lowercase letters, uppercase letters, digits, symbols.",
+            policy.min_classes
+        )));
+    }
+
+    if policy.denylist.contains(&password.to_lowercase()) {
+        return Err(dto::Error::BadRequest(
+            "Password is too common, choose another one.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Хеширует пароль настроенным алгоритмом.
+pub async fn hash(password: &str) -> Result<String, dto::Error> {
+    let hasher = hasher();
+    let (algorithm, cost) = (hasher.algorithm, hasher.bcrypt_cost);
+    let password = password.to_string();
+    run_blocking(move || match algorithm {
+        PasswordAlgorithm::Bcrypt => bcrypt::hash(password, cost).map_err(|err| err.to_string()),
+        PasswordAlgorithm::Argon2id => {
+            let salt = SaltString::generate(&mut argon2::password_hash::rand_core::OsRng);
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|err| err.to_string())
+        }
+    })
+    .await
+}
+
+/// Проверяет пароль по хешу любого поддерживаемого алгоритма.
+pub async fn verify(password: &str, hash: &str) -> Result<bool, dto::Error> {
+    let password = password.to_string();
+    let hash = hash.to_string();
+    run_blocking(move || {
+        if hash.starts_with("$argon2") {
+            let parsed = PasswordHash::new(&hash).map_err(|err| err.to_string())?;
+            Ok(Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok())
+        } else {
+            bcrypt::verify(password, &hash).map_err(|err| err.to_string())
+        }
+    })
+    .await
+}
+
+/// Проверка пароля, когда сотрудника нет: занимает столько же времени, сколько настоящая,
+/// чтобы по времени ответа нельзя было узнать, какие email зарегистрированы.
+///
+/// Хеш для сравнения считается один раз текущим алгоритмом и стоимостью.
+pub async fn verify_dummy(password: &str) -> Result<(), dto::Error> {
+    let dummy = DUMMY_HASH
+        .get_or_try_init(|| hash("dummy-password-for-unknown-accounts"))
+        .await?;
+    verify(password, dummy).await.map(|_| ())
+}
+
+/// Нужно ли пересчитать хеш: другой алгоритм, стоимость bcrypt или параметры Argon2.
+pub fn needs_rehash(hash: &str) -> bool {
+    let hasher = hasher();
+    match hasher.algorithm {
+        PasswordAlgorithm::Bcrypt => match hash.parse::<bcrypt::HashParts>() {
+            Ok(parts) => parts.get_cost() != hasher.bcrypt_cost,
+            Err(_) => true,
+        },
+        PasswordAlgorithm::Argon2id => match PasswordHash::new(hash) {
+            Ok(parsed) if parsed.algorithm == argon2::Algorithm::Argon2id.ident() => {
+                let current = Params::default();
+                Params::try_from(&parsed).map_or(true, |params| {
+                    (params.m_cost(), params.t_cost(), params.p_cost())
+                        != (current.m_cost(), current.t_cost(), current.p_cost())
+                })
+            }
+            _ => true,
+        },
+    }
+}
+
+async fn run_blocking<T, F>(work: F) -> Result<T, dto::Error>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    let _permit =
+        hasher().permits.acquire().await.map_err(|err| {
+            dto::Error::InternalServerError(format!("Password hasher error: {err}"))
+        })?;
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|err| dto::Error::InternalServerError(format!("Password hasher error: {err}")))?
+        .map_err(|err| dto::Error::InternalServerError(format!("Password hasher error: {err}")))
+}
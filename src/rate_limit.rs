@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, MatchedPath, State};
+use axum::http::{HeaderValue, Method, Request, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::config::{RateLimitConfig, RateLimitStore};
+use crate::models::dto;
+use crate::telemetry;
+
+/// Маршруты, которые ограничиваются по IP клиента: вход, обновление токенов,
+/// восстановление пароля и создание учётных записей.
+const PROTECTED: &[(Method, &str)] = &[
+    (Method::POST, "/auth/login"),
+    (Method::POST, "/auth/login/2fa"),
+    (Method::POST, "/auth/refresh"),
+    (Method::POST, "/auth/password/forgot"),
+    (Method::POST, "/auth/password/reset"),
+    (Method::POST, "/employee"),
+    (Method::POST, "/import/employees"),
+];
+
+/// Сколько корзин держать в памяти, прежде чем выбросить полные.
+const MEMORY_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Token bucket: корзина вмещает `capacity` запросов и пополняется равномерно
+/// на `capacity` токенов в минуту.
+///
+/// ## Пример
+/// ```ignore
+/// let limiter = RateLimiter::in_memory(5);
+/// if let Err(retry_after) = limiter.check("account:ivan@example.com").await { ... }
+/// ```
+pub struct RateLimiter {
+    capacity: f64,
+    store: Store,
+}
+
+enum Store {
+    Disabled,
+    Memory(Mutex<HashMap<String, (f64, Instant)>>),
+    Postgres(PgPool),
+}
+
+impl RateLimiter {
+    pub fn in_memory(per_minute: u32) -> Self {
+        Self::new(per_minute, Store::Memory(Mutex::new(HashMap::new())))
+    }
+
+    /// Счётчики в таблице `rate_limit`, общие для всех экземпляров сервиса.
+    pub fn postgres(per_minute: u32, pool: &PgPool) -> Self {
+        Self::new(per_minute, Store::Postgres(pool.clone()))
+    }
+
+    pub fn from_config(config: &RateLimitConfig, per_minute: u32, pool: &PgPool) -> Self {
+        match config.store {
+            RateLimitStore::Memory => Self::in_memory(per_minute),
+            RateLimitStore::Postgres => Self::postgres(per_minute, pool),
+        }
+    }
+
+    fn new(per_minute: u32, store: Store) -> Self {
+        RateLimiter {
+            capacity: per_minute as f64,
+            store: if per_minute == 0 {
+                Store::Disabled
+            } else {
+                store
+            },
+        }
+    }
+
+    /// Забирает токен из корзины `key`. `Err` - через сколько появится следующий.
+    ///
+    /// Если Postgres недоступен, запрос пропускается: ограничение частоты не должно
+    /// останавливать вход вместе с базой.
+    pub async fn check(&self, key: &str) -> Result<(), Duration> {
+        match &self.store {
+            Store::Disabled => Ok(()),
+            Store::Memory(buckets) => {
+                let mut buckets = buckets.lock().unwrap();
+                let now = Instant::now();
+                if buckets.len() > MEMORY_PRUNE_THRESHOLD {
+                    let capacity = self.capacity;
+                    let rate = self.rate();
+                    buckets.retain(|_, (tokens, at)| {
+                        *tokens + now.duration_since(*at).as_secs_f64() * rate < capacity
+                    });
+                }
+                let (tokens, at) = buckets.get(key).copied().unwrap_or((self.capacity, now));
+                let (tokens, result) = self.take(tokens, now.duration_since(at));
+                buckets.insert(key.to_string(), (tokens, now));
+                result
+            }
+            Store::Postgres(pool) => match self.check_postgres(pool, key).await {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::error!("Rate limit store error: {err}");
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    async fn check_postgres(
+        &self,
+        pool: &PgPool,
+        key: &str,
+    ) -> Result<Result<(), Duration>, sqlx::Error> {
+        let _timer = telemetry::query_timer("rate_limit", "check");
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO rate_limit (key, tokens, updated_at) VALUES ($1, $2, CURRENT_TIMESTAMP)
+            ON CONFLICT (key) DO NOTHING",
+        )
+        .bind(key)
+        .bind(self.capacity)
+        .execute(&mut *tx)
+        .await?;
+        // Блокировка строки сериализует одновременные запросы с разных экземпляров
+        let (tokens, updated_at, now): (f64, DateTime<Utc>, DateTime<Utc>) = sqlx::query_as(
+            "SELECT tokens, updated_at, CURRENT_TIMESTAMP FROM rate_limit WHERE key = $1 FOR UPDATE",
+        )
+        .bind(key)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let elapsed = (now - updated_at).to_std().unwrap_or_default();
+        let (tokens, result) = self.take(tokens, elapsed);
+        sqlx::query("UPDATE rate_limit SET tokens = $2, updated_at = $3 WHERE key = $1")
+            .bind(key)
+            .bind(tokens)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    fn rate(&self) -> f64 {
+        self.capacity / 60.0
+    }
+
+    /// Пополняет корзину за прошедшее время и забирает токен, если он есть.
+    fn take(&self, tokens: f64, elapsed: Duration) -> (f64, Result<(), Duration>) {
+        let tokens = (tokens + elapsed.as_secs_f64() * self.rate()).min(self.capacity);
+        if tokens >= 1.0 {
+            (tokens - 1.0, Ok(()))
+        } else {
+            let wait = (1.0 - tokens) / self.rate();
+            (tokens, Err(Duration::from_secs_f64(wait)))
+        }
+    }
+}
+
+/// Ограничивает частоту запросов к маршрутам из `PROTECTED` по IP клиента.
+pub fn protect(router: Router, limiter: Arc<RateLimiter>, trust_forwarded_for: bool) -> Router {
+    router.layer(axum::middleware::from_fn_with_state(
+        (limiter, trust_forwarded_for),
+        limit_by_ip,
+    ))
+}
+
+async fn limit_by_ip(
+    State((limiter, trust_forwarded_for)): State<(Arc<RateLimiter>, bool)>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(route) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str())
+        .filter(|route| {
+            PROTECTED
+                .iter()
+                .any(|(method, path)| method == request.method() && path == route)
+        })
+    else {
+        return next.run(request).await;
+    };
+
+    let key = format!("ip:{route}:{}", client_ip(&request, trust_forwarded_for));
+    match limiter.check(&key).await {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limit exceeded for {key}");
+            too_many_requests(retry_after)
+        }
+    }
+}
+
+/// IP клиента: из `X-Forwarded-For`, если прокси доверенный, иначе адрес соединения.
+///
+/// Берётся последний адрес в заголовке - его дописал наш прокси. Всё левее клиент
+/// может прислать сам, и с новым адресом на каждый запрос получал бы новую корзину.
+fn client_ip(request: &Request<Body>, trust_forwarded_for: bool) -> String {
+    let forwarded = trust_forwarded_for
+        .then(|| request.headers().get("x-forwarded-for"))
+        .flatten()
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.rsplit(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+    forwarded
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| info.0.ip().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Ответ `429` с заголовком `Retry-After` в целых секундах.
+pub fn too_many_requests(retry_after: Duration) -> Response {
+    let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let (status, body) =
+        dto::Error::TooManyRequests("Too many requests".to_string(), retry_after).into_response();
+    (
+        status,
+        [(header::RETRY_AFTER, HeaderValue::from(seconds))],
+        body,
+    )
+        .into_response()
+}
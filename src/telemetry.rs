@@ -0,0 +1,92 @@
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::MatchedPath;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+// Границы корзин гистограмм в секундах: от быстрых запросов по индексу до тяжёлых отчётов
+const BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
+
+// Вызовы методов репозиториев, которые сейчас выполняются, см. `queries_in_flight`
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Глобальный рекордер метрик. Устанавливается при первом вызове, в том числе в тестах.
+pub fn prometheus() -> &'static PrometheusHandle {
+    PROMETHEUS.get_or_init(|| {
+        PrometheusBuilder::new()
+            .set_buckets(BUCKETS)
+            .expect("Buckets are not empty")
+            .install_recorder()
+            .expect("Failed to install Prometheus recorder")
+    })
+}
+
+/// Замеряет время запроса к базе до конца области видимости и до тех пор считает
+/// запрос выполняющимся.
+///
+/// ```ignore
+/// let _timer = telemetry::query_timer("services", "get_all_services");
+/// ```
+pub fn query_timer(repo: &'static str, method: &'static str) -> QueryTimer {
+    IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+    QueryTimer {
+        repo,
+        method,
+        started: Instant::now(),
+    }
+}
+
+pub struct QueryTimer {
+    repo: &'static str,
+    method: &'static str,
+    started: Instant,
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        metrics::histogram!(
+            "db_query_duration_seconds",
+            "repo" => self.repo,
+            "method" => self.method,
+        )
+        .record(self.started.elapsed().as_secs_f64());
+        IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Сколько методов репозиториев выполняется сейчас, то есть держат соединение или
+/// ждут его из пула. sqlx не отдаёт длину очереди пула, поэтому ожидающие
+/// оцениваются как разница этого числа и занятых соединений.
+pub fn queries_in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::Relaxed)
+}
+
+/// Счётчик и гистограмма HTTP запросов по шаблону маршрута, а не по фактическому пути,
+/// чтобы `/services/1` и `/services/2` не плодили отдельные ряды.
+pub async fn track_http(request: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |p| p.as_str().to_string());
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16().to_string();
+    let labels = [("method", method), ("route", route), ("status", status)];
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!("http_request_duration_seconds", &labels)
+        .record(started.elapsed().as_secs_f64());
+
+    response
+}
@@ -0,0 +1,878 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::header;
+use axum::routing::post;
+use axum::{Extension, Json, Router, http::StatusCode};
+use mds_backend_rust::config::{PasswordAlgorithm, SecurityConfig};
+use mds_backend_rust::features::auth::{Authenticator, Keys, Principal, Settings};
+use mds_backend_rust::mail::MemoryMailer;
+use mds_backend_rust::models::dao::Permission;
+use mds_backend_rust::rate_limit::{self, RateLimiter};
+use mds_backend_rust::{features, i18n, logger, models::dto, password};
+use serde_json::json;
+use sqlx::PgPool;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const PASSWORD: &str = "Qwerty123";
+
+/// Сотрудник с минимальной стоимостью bcrypt, чтобы тесты не тратили время на хеширование.
+async fn insert_employee(pool: &PgPool, email: &str, role: i32) -> i64 {
+    let password = bcrypt::hash(PASSWORD, 4).unwrap();
+    sqlx::query_scalar(
+        "INSERT INTO employee (name, last_name, email, password, role, active)
+        VALUES ('Иван', 'Петров', $1, $2, $3, true)
+        RETURNING id",
+    )
+    .bind(email)
+    .bind(password)
+    .bind(role)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// Код из приложения-аутентификатора на `offset` шагов вперёд.
+fn totp_code(secret: &str, offset: u64) -> String {
+    let bytes = Secret::Encoded(secret.to_string()).to_bytes().unwrap();
+    let totp = TOTP::new(Algorithm::SHA1, 6, 1, 30, bytes, None, String::new()).unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    totp.generate(now + offset * 30)
+}
+
+/// Маршрут, доступный сотрудникам и ключам с правом `requests:create`.
+async fn probe(principal: Principal) -> Result<StatusCode, (StatusCode, Json<dto::ErrorResponse>)> {
+    principal
+        .require(Permission::RequestsCreate)
+        .map_err(|err| err.into_response())?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn app(
+    pool: &PgPool,
+    account_limiter: RateLimiter,
+    ip_limiter: RateLimiter,
+    mailer: MemoryMailer,
+) -> Router {
+    app_with_2fa(pool, account_limiter, ip_limiter, mailer, false)
+}
+
+/// `require_2fa` - обязательная 2FA для менеджеров и суперадминов.
+fn app_with_2fa(
+    pool: &PgPool,
+    account_limiter: RateLimiter,
+    ip_limiter: RateLimiter,
+    mailer: MemoryMailer,
+    require_2fa: bool,
+) -> Router {
+    // Та же стоимость, что у тестовых хешей, иначе вход пересчитывает их с 14
+    password::configure(&SecurityConfig {
+        bcrypt_cost: 4,
+        password_algorithm: PasswordAlgorithm::Bcrypt,
+        hash_concurrency: 2,
+        jwt_secret: None,
+        jwt_refresh_secret: None,
+        refresh_token_ttl: Duration::from_secs(3600),
+        require_2fa,
+        totp_issuer: "MDS".to_string(),
+        lockout_threshold: 3,
+        password_min_length: 8,
+        password_min_classes: 3,
+        password_denylist: None,
+        password_reset_ttl: Duration::from_secs(3600),
+    })
+    .unwrap();
+    let keys = Arc::new(
+        Keys::new("test-secret-test-secret-test-secret")
+            .with_refresh_secret("refresh-secret-refresh-secret-refresh"),
+    );
+    let authenticator =
+        Arc::new(Authenticator::new(pool, keys.clone()).with_required_2fa(require_2fa));
+    let settings = Settings::new(keys.clone())
+        .with_limiter(Arc::new(account_limiter))
+        .with_lockout_threshold(3)
+        .with_mailer(Arc::new(mailer))
+        .with_password_reset(
+            Duration::from_secs(3600),
+            Some("https://mds.example.com/reset?token={token}".to_string()),
+        )
+        .with_two_factor("MDS", require_2fa);
+    let router = Router::new()
+        .merge(features::auth::new(pool, settings))
+        .merge(features::api_keys::new(pool))
+        .merge(features::employee::new(pool))
+        .route("/probe", post(probe));
+    rate_limit::protect(router, Arc::new(ip_limiter), false)
+        .layer(axum::middleware::from_fn(i18n::negotiate))
+        .layer(Extension(authenticator))
+}
+
+#[sqlx::test]
+async fn test_login_lockout_and_unlock(pool: PgPool) {
+    println!("Testing login, lockout after failed attempts and unlock by superadmin");
+    logger::init_dev_logger();
+
+    let employee = insert_employee(&pool, "ivan@example.com", 0).await;
+    insert_employee(&pool, "manager@example.com", 1).await;
+    insert_employee(&pool, "admin@example.com", 2).await;
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        MemoryMailer::default(),
+    ))
+    .unwrap();
+
+    let login = |email: &str, password: &str| {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": password }))
+    };
+
+    // Unknown email and wrong password look the same
+    let response = login("nobody@example.com", PASSWORD).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    for _ in 0..3 {
+        let response = login("ivan@example.com", "wrong").await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Lock isn't revealed without the password, but holds with the right one
+    let response = login("ivan@example.com", "wrong").await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = login("ivan@example.com", PASSWORD).await;
+    assert_eq!(response.status_code(), StatusCode::LOCKED);
+
+    // Unlock requires a superadmin token
+    let unlock = format!("/employee/{employee}/unlock");
+    let response = server.post(&unlock).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = server
+        .post(&unlock)
+        .authorization_bearer("not-a-token")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    let manager: dto::Token = login("manager@example.com", PASSWORD).await.json();
+    assert_eq!(manager.token_type, "Bearer");
+    let response = server
+        .post(&unlock)
+        .authorization_bearer(&manager.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let admin: dto::Token = login("admin@example.com", PASSWORD).await.json();
+    let response = server
+        .post(&unlock)
+        .authorization_bearer(&admin.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = login("ivan@example.com", PASSWORD).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Deactivated employee can't log in
+    sqlx::query("UPDATE employee SET active = false WHERE id = $1")
+        .bind(employee)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = login("ivan@example.com", PASSWORD).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_rate_limit_by_account_and_ip(pool: PgPool) {
+    println!("Testing rate limiting by account and by IP");
+    logger::init_dev_logger();
+
+    insert_employee(&pool, "ivan@example.com", 0).await;
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(2),
+        RateLimiter::in_memory(4),
+        MemoryMailer::default(),
+    ))
+    .unwrap();
+
+    // By account
+    for _ in 0..2 {
+        let response = server
+            .post("/auth/login")
+            .json(&json!({ "email": "ivan@example.com", "password": "wrong" }))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+    let response = server
+        .post("/auth/login")
+        .json(&json!({ "email": "IVAN@example.com", "password": PASSWORD }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response
+        .header("retry-after")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+
+    // By IP, other accounts are limited too
+    let response = server
+        .post("/auth/login")
+        .json(&json!({ "email": "other@example.com", "password": "wrong" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = server
+        .post("/auth/login")
+        .json(&json!({ "email": "another@example.com", "password": "wrong" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.maybe_header("retry-after").is_some());
+
+    // Employee creation has its own bucket and reaches the login check
+    let response = server.post("/employee").json(&json!({})).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_rate_limit_postgres_store(pool: PgPool) {
+    println!("Testing shared Postgres rate limit store");
+    logger::init_dev_logger();
+
+    let first = RateLimiter::postgres(2, &pool);
+    let second = RateLimiter::postgres(2, &pool);
+
+    // Both instances share the same bucket
+    assert!(first.check("ip:/auth/login:10.0.0.1").await.is_ok());
+    assert!(second.check("ip:/auth/login:10.0.0.1").await.is_ok());
+    let retry_after = first.check("ip:/auth/login:10.0.0.1").await.unwrap_err();
+    assert!(retry_after.as_secs() <= 30);
+    assert!(second.check("ip:/auth/login:10.0.0.2").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_rate_limit_forwarded_for() {
+    println!("Testing IP rate limit behind a proxy with spoofed X-Forwarded-For");
+    logger::init_dev_logger();
+
+    let router = Router::new().route("/auth/login", post(|| async { StatusCode::NO_CONTENT }));
+    let server = axum_test::TestServer::new(rate_limit::protect(
+        router,
+        Arc::new(RateLimiter::in_memory(2)),
+        true,
+    ))
+    .unwrap();
+    let login = |forwarded_for: String| {
+        server
+            .post("/auth/login")
+            .add_header("x-forwarded-for", forwarded_for)
+    };
+
+    // A new fake address on the left doesn't give a new bucket
+    for i in 0..2 {
+        let response = login(format!("10.0.0.{i}, 203.0.113.7")).await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+    let response = login("10.0.0.9, 203.0.113.7".to_string()).await;
+    assert_eq!(response.status_code(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The address appended by the proxy decides
+    let response = login("10.0.0.9, 203.0.113.8".to_string()).await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+}
+
+#[sqlx::test]
+async fn test_change_and_reset_password(pool: PgPool) {
+    println!("Testing password change and reset by emailed token");
+    logger::init_dev_logger();
+
+    let employee = insert_employee(&pool, "ivan@example.com", 0).await;
+    let mailer = MemoryMailer::default();
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        mailer.clone(),
+    ))
+    .unwrap();
+    let login = |password: &str| {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": "ivan@example.com", "password": password }))
+    };
+
+    // Change requires a token, the current password and a policy-compliant new one
+    let change = |current: &str, new: &str| {
+        server
+            .post("/auth/password")
+            .json(&json!({ "current_password": current, "new_password": new }))
+    };
+    let response = change(PASSWORD, "Changed-123").await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let token: dto::Token = login(PASSWORD).await.json();
+    let response = change("wrong", "Changed-123")
+        .authorization_bearer(&token.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = change(PASSWORD, "short")
+        .authorization_bearer(&token.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = change(PASSWORD, "Changed-123")
+        .authorization_bearer(&token.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        login(PASSWORD).await.status_code(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(login("Changed-123").await.status_code(), StatusCode::OK);
+
+    // Unknown email gets the same answer and no mail
+    let forgot = |email: &str| {
+        server
+            .post("/auth/password/forgot")
+            .json(&json!({ "email": email }))
+    };
+    let response = forgot("nobody@example.com").await;
+    assert_eq!(response.status_code(), StatusCode::ACCEPTED);
+
+    // Only the latest token is valid
+    forgot("ivan@example.com").await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let response = forgot("ivan@example.com").await;
+    assert_eq!(response.status_code(), StatusCode::ACCEPTED);
+    // Mail is sent in the background
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[1].to, "ivan@example.com");
+    let token_of = |body: &str| {
+        body.split("token=")
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    };
+    let (old, token) = (token_of(&sent[0].body), token_of(&sent[1].body));
+
+    // Only the hash is stored
+    let stored: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM password_reset WHERE token_hash = $1")
+            .bind(&token)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, 0);
+
+    let reset = |token: &str, password: &str| {
+        server
+            .post("/auth/password/reset")
+            .json(&json!({ "token": token, "new_password": password }))
+    };
+    let response = reset(&old, "Restored-123").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = reset(&token, "weak").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Reset also unlocks an account locked by failed logins
+    for _ in 0..3 {
+        login("wrong").await;
+    }
+    assert_eq!(login("Changed-123").await.status_code(), StatusCode::LOCKED);
+    let response = reset(&token, "Restored-123").await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    assert_eq!(login("Restored-123").await.status_code(), StatusCode::OK);
+
+    // Single use
+    let response = reset(&token, "Another-123").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Expired
+    forgot("ivan@example.com").await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let expired = token_of(&mailer.sent()[2].body);
+    sqlx::query(
+        "UPDATE password_reset SET expires_at = now() - interval '1 minute' WHERE employee_id = $1",
+    )
+    .bind(employee)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let response = reset(&expired, "Another-123").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_sessions_refresh_rotation_and_revocation(pool: PgPool) {
+    println!("Testing sessions, refresh token rotation, reuse detection and revocation");
+    logger::init_dev_logger();
+
+    let employee = insert_employee(&pool, "ivan@example.com", 0).await;
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        MemoryMailer::default(),
+    ))
+    .unwrap();
+    let login = |agent: &str| {
+        server
+            .post("/auth/login")
+            .add_header("user-agent", agent)
+            .json(&json!({ "email": "ivan@example.com", "password": PASSWORD }))
+    };
+    let refresh = |token: &str| {
+        server
+            .post("/auth/refresh")
+            .json(&json!({ "refresh_token": token }))
+    };
+    let sessions = |token: &str| server.get("/auth/sessions").authorization_bearer(token);
+
+    let phone: dto::Token = login("phone").await.json();
+    let laptop: dto::Token = login("laptop").await.json();
+
+    let list: Vec<dto::Session> = sessions(&laptop.access_token).await.json();
+    assert_eq!(list.len(), 2);
+    let current: Vec<_> = list.iter().filter(|s| s.current).collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0].user_agent.as_deref(), Some("laptop"));
+
+    // Access token can't be used as a refresh token
+    let response = refresh(&phone.access_token).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Rotation: the new pair works, the old refresh token is spent
+    let rotated: dto::Token = refresh(&phone.refresh_token).await.json();
+    assert_ne!(rotated.refresh_token, phone.refresh_token);
+    let response = sessions(&rotated.access_token).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Reuse of the spent token revokes the whole session
+    let response = refresh(&phone.refresh_token).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = refresh(&rotated.refresh_token).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = sessions(&rotated.access_token).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let list: Vec<dto::Session> = sessions(&laptop.access_token).await.json();
+    assert_eq!(list.len(), 1);
+
+    // Logout ends only the current session
+    let tablet: dto::Token = login("tablet").await.json();
+    let response = server
+        .post("/auth/logout")
+        .authorization_bearer(&tablet.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        sessions(&tablet.access_token).await.status_code(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        sessions(&laptop.access_token).await.status_code(),
+        StatusCode::OK
+    );
+
+    // Logout everywhere
+    let desktop: dto::Token = login("desktop").await.json();
+    let response = server
+        .delete("/auth/sessions")
+        .authorization_bearer(&desktop.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    for token in [&laptop, &desktop] {
+        assert_eq!(
+            sessions(&token.access_token).await.status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            refresh(&token.refresh_token).await.status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    // Deactivated employee is rejected even with a live session
+    let token: dto::Token = login("phone").await.json();
+    sqlx::query("UPDATE employee SET active = false WHERE id = $1")
+        .bind(employee)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        sessions(&token.access_token).await.status_code(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        refresh(&token.refresh_token).await.status_code(),
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[sqlx::test]
+async fn test_api_keys(pool: PgPool) {
+    println!("Testing API keys managed by superadmin");
+    logger::init_dev_logger();
+
+    insert_employee(&pool, "ivan@example.com", 0).await;
+    insert_employee(&pool, "admin@example.com", 2).await;
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        MemoryMailer::default(),
+    ))
+    .unwrap();
+    let login = |email: &str| {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": PASSWORD }))
+    };
+    let employee: dto::Token = login("ivan@example.com").await.json();
+    let admin: dto::Token = login("admin@example.com").await.json();
+    let create = |token: &str, body: serde_json::Value| {
+        server
+            .post("/api-keys")
+            .authorization_bearer(token)
+            .json(&body)
+    };
+
+    // Only superadmin manages keys
+    let body = json!({ "name": "monitoring", "permissions": ["requests:create"] });
+    let response = create(&employee.access_token, body.clone()).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    let response = create(
+        &admin.access_token,
+        json!({ "name": "x", "permissions": ["root"] }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = create(
+        &admin.access_token,
+        json!({ "name": "x", "permissions": ["requests:create"], "expires_at": "2020-01-01T00:00:00Z" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = create(&admin.access_token, body).await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let monitoring: dto::ApiKey = response.json();
+    let key = monitoring.key.unwrap();
+    assert!(key.starts_with("mds_"));
+    assert!(key.starts_with(monitoring.prefix.as_deref().unwrap()));
+
+    // Employees have the permission by role, keys by scope
+    let response = server
+        .post("/probe")
+        .authorization_bearer(&employee.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    let response = server.post("/probe").authorization_bearer(&key).await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    let response = server
+        .post("/probe")
+        .authorization_bearer("mds_unknown")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // A key is not an employee
+    let response = server
+        .get("/auth/sessions")
+        .authorization_bearer(&key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Listing hides the key and shows when it was used
+    let keys: Vec<dto::ApiKey> = server
+        .get("/api-keys")
+        .authorization_bearer(&admin.access_token)
+        .await
+        .json();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].key, None);
+    assert!(keys[0].last_used_at.is_some());
+    let stored: i64 = sqlx::query_scalar("SELECT count(*) FROM api_key WHERE key_hash = $1")
+        .bind(&key)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+
+    // Expired and revoked keys stop working
+    let response = create(
+        &admin.access_token,
+        json!({ "name": "short", "permissions": ["requests:create"], "expires_at": "2999-01-01T00:00:00Z" }),
+    )
+    .await;
+    let short: dto::ApiKey = response.json();
+    sqlx::query("UPDATE api_key SET expires_at = now() - interval '1 second' WHERE id = $1")
+        .bind(short.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = server
+        .post("/probe")
+        .authorization_bearer(short.key.as_deref().unwrap())
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .delete(&format!("/api-keys/{}", monitoring.id.unwrap()))
+        .authorization_bearer(&admin.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = server.post("/probe").authorization_bearer(&key).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_two_factor_login_and_recovery_codes(pool: PgPool) {
+    println!("Testing TOTP enrollment, second login step, code replay and recovery codes");
+    logger::init_dev_logger();
+
+    insert_employee(&pool, "manager@example.com", 1).await;
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        MemoryMailer::default(),
+    ))
+    .unwrap();
+    let login = || {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": "manager@example.com", "password": PASSWORD }))
+    };
+    let token: dto::Token = login().await.json();
+
+    // Enrollment: the secret only works after a valid code
+    let setup: dto::TotpSetup = server
+        .post("/auth/2fa/setup")
+        .authorization_bearer(&token.access_token)
+        .await
+        .json();
+    assert!(setup.provisioning_uri.starts_with("otpauth://totp/MDS:"));
+    assert!(setup.provisioning_uri.contains(&setup.secret));
+    let enable = |code: &str| {
+        server
+            .post("/auth/2fa/enable")
+            .authorization_bearer(&token.access_token)
+            .json(&json!({ "code": code }))
+    };
+    let response = enable("000000").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = enable(&totp_code(&setup.secret, 0)).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let codes: dto::RecoveryCodes = response.json();
+    assert_eq!(codes.recovery_codes.len(), 10);
+    let response = server
+        .post("/auth/2fa/setup")
+        .authorization_bearer(&token.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+
+    // Recovery codes are stored hashed
+    let stored: i64 = sqlx::query_scalar("SELECT count(*) FROM recovery_code WHERE code_hash = $1")
+        .bind(&codes.recovery_codes[0])
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+
+    // The password alone gives a challenge instead of tokens
+    let response = login().await;
+    assert_eq!(response.status_code(), StatusCode::ACCEPTED);
+    let challenge: dto::MfaChallenge = response.json();
+    let second_step = |body: serde_json::Value| server.post("/auth/login/2fa").json(&body);
+
+    let response = second_step(json!({ "mfa_token": token.access_token, "code": "123456" })).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = second_step(json!({ "mfa_token": challenge.mfa_token, "code": "000000" })).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // A code already accepted at enrollment can't be replayed
+    let code = totp_code(&setup.secret, 0);
+    let response = second_step(json!({ "mfa_token": challenge.mfa_token, "code": code })).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let code = totp_code(&setup.secret, 1);
+    let response = second_step(json!({ "mfa_token": challenge.mfa_token, "code": code })).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let mfa: dto::Token = response.json();
+    let response = server
+        .get("/auth/sessions")
+        .authorization_bearer(&mfa.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Recovery code works once, in any case and with or without dashes
+    let recovery = codes.recovery_codes[1].to_uppercase().replace('-', "");
+    let body = json!({ "mfa_token": challenge.mfa_token, "recovery_code": recovery });
+    let response = second_step(body.clone()).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = second_step(body).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Failed second steps count towards the lockout
+    for _ in 0..2 {
+        let body = json!({ "mfa_token": challenge.mfa_token, "code": "000000" });
+        let response = second_step(body).await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+    let response = second_step(json!({ "mfa_token": challenge.mfa_token, "code": "000000" })).await;
+    assert_eq!(response.status_code(), StatusCode::LOCKED);
+
+    // Disabling needs a valid code
+    let disable = |body: serde_json::Value| {
+        server
+            .post("/auth/2fa/disable")
+            .authorization_bearer(&mfa.access_token)
+            .json(&body)
+    };
+    let response = disable(json!({ "code": "000000" })).await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = disable(json!({ "recovery_code": codes.recovery_codes[2] })).await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    let remaining: i64 = sqlx::query_scalar("SELECT count(*) FROM recovery_code")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[sqlx::test]
+async fn test_two_factor_required_for_privileged_roles(pool: PgPool) {
+    println!("Testing mandatory 2FA for managers and superadmins");
+    logger::init_dev_logger();
+
+    insert_employee(&pool, "ivan@example.com", 0).await;
+    insert_employee(&pool, "admin@example.com", 2).await;
+    let server = axum_test::TestServer::new(app_with_2fa(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        MemoryMailer::default(),
+        true,
+    ))
+    .unwrap();
+    let login = |email: &str| {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": PASSWORD }))
+    };
+
+    // Employees are not affected
+    let employee: dto::Token = login("ivan@example.com").await.json();
+    let response = server
+        .post("/probe")
+        .authorization_bearer(&employee.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    // A superadmin without 2FA can log in, but only to enroll
+    let admin: dto::Token = login("admin@example.com").await.json();
+    let response = server
+        .get("/api-keys")
+        .authorization_bearer(&admin.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    let setup: dto::TotpSetup = server
+        .post("/auth/2fa/setup")
+        .authorization_bearer(&admin.access_token)
+        .await
+        .json();
+    let response = server
+        .post("/auth/2fa/enable")
+        .authorization_bearer(&admin.access_token)
+        .json(&json!({ "code": totp_code(&setup.secret, 0) }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let codes: dto::RecoveryCodes = response.json();
+
+    // The same token works once 2FA is enabled
+    let response = server
+        .get("/api-keys")
+        .authorization_bearer(&admin.access_token)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // And it can't be turned off while the policy is on
+    let response = server
+        .post("/auth/2fa/disable")
+        .authorization_bearer(&admin.access_token)
+        .json(&json!({ "recovery_code": codes.recovery_codes[0] }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_locale_preference(pool: PgPool) {
+    println!("Testing employee locale preference for errors and mail");
+    logger::init_dev_logger();
+
+    insert_employee(&pool, "ivan@example.com", 0).await;
+    let mailer = MemoryMailer::default();
+    let server = axum_test::TestServer::new(app(
+        &pool,
+        RateLimiter::in_memory(0),
+        RateLimiter::in_memory(0),
+        mailer.clone(),
+    ))
+    .unwrap();
+    let token: dto::Token = server
+        .post("/auth/login")
+        .json(&json!({ "email": "ivan@example.com", "password": PASSWORD }))
+        .await
+        .json();
+    let forbidden = |accept_language: Option<&str>| {
+        let request = server
+            .get("/api-keys")
+            .authorization_bearer(&token.access_token);
+        match accept_language {
+            Some(value) => request.add_header(header::ACCEPT_LANGUAGE, value),
+            None => request,
+        }
+    };
+    let message = |response: axum_test::TestResponse| {
+        assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+        response.json::<dto::ErrorResponse>().error
+    };
+
+    // Without a preference the header decides
+    assert_eq!(message(forbidden(None).await), "Not enough permissions");
+    assert_eq!(message(forbidden(Some("ru")).await), "Недостаточно прав");
+
+    let set_locale = |locale: serde_json::Value| {
+        server
+            .put("/auth/locale")
+            .authorization_bearer(&token.access_token)
+            .json(&json!({ "locale": locale }))
+    };
+    let response = set_locale(json!("de")).await;
+    assert_eq!(response.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    let response = set_locale(json!("ru")).await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    // The preference wins over the header
+    let response = forbidden(Some("en")).await;
+    assert_eq!(response.header(header::CONTENT_LANGUAGE), "ru");
+    assert_eq!(message(response), "Недостаточно прав");
+
+    // Mail is sent in the employee's language
+    server
+        .post("/auth/password/forgot")
+        .json(&json!({ "email": "ivan@example.com" }))
+        .await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let sent = mailer.sent();
+    assert_eq!(sent[0].subject, "Восстановление пароля");
+    assert!(sent[0].body.contains("Ссылка действует 60 мин."));
+
+    let response = set_locale(serde_json::Value::Null).await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    assert_eq!(message(forbidden(None).await), "Not enough permissions");
+}
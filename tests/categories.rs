@@ -0,0 +1,232 @@
+use axum::http::StatusCode;
+use mds_backend_rust::{
+    features, logger,
+    models::dto::{self, Category, CategoryNode},
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+fn server(pool: &PgPool) -> axum_test::TestServer {
+    let app = features::categories::new(pool).merge(features::services::new(pool));
+    axum_test::TestServer::new(app).unwrap()
+}
+
+async fn create_category(
+    server: &axum_test::TestServer,
+    name: &str,
+    parent_id: Option<i64>,
+) -> i64 {
+    let response = server
+        .post("/categories")
+        .json(&Category::new(name, parent_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    response.json::<Category>().id.unwrap()
+}
+
+async fn create_service(server: &axum_test::TestServer, name: &str, category_id: i64) -> i64 {
+    let response = server
+        .post("/services")
+        .json(&json!({"name": name, "category_id": category_id}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    response.json::<dto::Service>().id.unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_category_tree(pool: PgPool) {
+    println!("Testing category tree with services");
+    logger::init_dev_logger();
+
+    let server = server(&pool);
+    let web = create_category(&server, "Web development", None).await;
+    let shops = create_category(&server, "E-commerce", Some(web)).await;
+    let design = create_category(&server, "Design", None).await;
+    create_service(&server, "Landing page", web).await;
+    create_service(&server, "Online store", shops).await;
+    server
+        .post("/services")
+        .json(&dto::Service::new(None, Some("Consulting".to_string())))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // Названия уникальны только среди соседей
+    create_category(&server, "Design", Some(web)).await;
+    let response = server
+        .post("/categories")
+        .json(&Category::new("E-commerce", Some(web)))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+
+    let response = server.get("/categories/tree").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let tree: Vec<CategoryNode> = response.json();
+    println!(
+        "Tree:\n{}",
+        serde_json::to_string_pretty(&tree).expect("Failed to format JSON")
+    );
+
+    let roots: Vec<(i64, &str)> = tree.iter().map(|n| (n.id, n.name.as_str())).collect();
+    assert_eq!(roots, vec![(design, "Design"), (web, "Web development")]);
+    let web_node = &tree[1];
+    let children: Vec<&str> = web_node.children.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(children, vec!["Design", "E-commerce"]);
+    assert_eq!(web_node.services.len(), 1);
+    assert_eq!(web_node.services[0].name, "Landing page");
+    assert_eq!(web_node.children[1].services[0].name, "Online store");
+
+    // Фильтр списка услуг включает подкатегории
+    let response = server
+        .get("/services")
+        .add_query_param("category_id", web)
+        .await;
+    let mut names: Vec<String> = response
+        .json::<Vec<dto::Service>>()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Landing page", "Online store"]);
+
+    let response = server
+        .get("/services")
+        .add_query_param("category_id", shops)
+        .await;
+    let services: Vec<dto::Service> = response.json();
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].category_id.flatten(), Some(shops));
+
+    let response = server.get("/services").await;
+    assert_eq!(response.json::<Vec<dto::Service>>().len(), 3);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_category_move_prevents_cycles(pool: PgPool) {
+    println!("Testing category move prevents cycles");
+    logger::init_dev_logger();
+
+    let server = server(&pool);
+    let root = create_category(&server, "Web development", None).await;
+    let child = create_category(&server, "E-commerce", Some(root)).await;
+    let grandchild = create_category(&server, "Marketplaces", Some(child)).await;
+
+    let cycle = "Category can't be moved into itself or its subcategory.".to_string();
+    for parent in [root, grandchild] {
+        let response = server
+            .put(&format!("/categories/{root}"))
+            .json(&Category::new("Web development", Some(parent)))
+            .await;
+        let body: dto::ErrorResponse = response.json();
+        assert_eq!(
+            (response.status_code(), body.error),
+            (StatusCode::BAD_REQUEST, cycle.clone())
+        );
+    }
+
+    let response = server
+        .put(&format!("/categories/{child}"))
+        .json(&Category::new("E-commerce", Some(999)))
+        .await;
+    let body: dto::ErrorResponse = response.json();
+    assert_eq!(
+        (response.status_code(), body.error),
+        (
+            StatusCode::BAD_REQUEST,
+            "Parent category with id: 999 not found".to_string()
+        )
+    );
+
+    // Перенос в корень и под бывшего потомка допустим
+    let response = server
+        .put(&format!("/categories/{grandchild}"))
+        .json(&Category::new("Marketplaces", None))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = server
+        .put(&format!("/categories/{root}"))
+        .json(&Category::new("Web development", Some(grandchild)))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(response.json::<Category>().parent_id, Some(grandchild));
+
+    let response = server
+        .put("/categories/999")
+        .json(&Category::new("Missing", None))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_delete_category(pool: PgPool) {
+    println!("Testing delete category");
+    logger::init_dev_logger();
+
+    let server = server(&pool);
+    let root = create_category(&server, "Web development", None).await;
+    let child = create_category(&server, "E-commerce", Some(root)).await;
+    let service = create_service(&server, "Online store", child).await;
+
+    let response = server.delete(&format!("/categories/{root}")).await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+
+    let response = server.delete(&format!("/categories/{child}")).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let service: dto::Service = server.get(&format!("/services/{service}")).await.json();
+    assert_eq!(service.category_id.flatten(), None);
+
+    let response = server.get(&format!("/categories/{child}")).await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_service_category_assignment(pool: PgPool) {
+    println!("Testing service category assignment");
+    logger::init_dev_logger();
+
+    let server = server(&pool);
+    let category = create_category(&server, "Web development", None).await;
+
+    let response = server
+        .post("/services")
+        .json(&json!({"name": "Landing page", "category_id": 999}))
+        .await;
+    let body: dto::ErrorResponse = response.json();
+    assert_eq!(
+        (response.status_code(), body.error),
+        (
+            StatusCode::BAD_REQUEST,
+            "Category with id: 999 not found".to_string()
+        )
+    );
+
+    // Без category_id услуга создаётся как раньше
+    let response = server
+        .post("/services")
+        .json(&json!({"name": "Landing page"}))
+        .await;
+    let service: dto::Service = response.json();
+    assert_eq!(service.category_id.flatten(), None);
+
+    let response = server
+        .put(&format!("/services/{}", service.id.unwrap()))
+        .json(&json!({"name": "Landing page", "category_id": category}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["category_id"], json!(category));
+
+    // Без category_id категория сохраняется, null убирает услугу из категории
+    let response = server
+        .put(&format!("/services/{}", service.id.unwrap()))
+        .json(&json!({"name": "Landing page", "active": false}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["category_id"], json!(category));
+    let response = server
+        .put(&format!("/services/{}", service.id.unwrap()))
+        .json(&json!({"name": "Landing page", "category_id": null}))
+        .await;
+    let body: Value = response.json();
+    assert_eq!(body["category_id"], Value::Null);
+}
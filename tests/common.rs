@@ -1,14 +1,20 @@
+#![allow(dead_code)]
+
 use std::sync::Arc;
 
+use axum::Extension;
+use mds_backend_rust::features::auth::token::digest;
+use mds_backend_rust::features::auth::{Authenticator, Keys};
 use mds_backend_rust::{features, models::dto};
 use sqlx::PgPool;
 
 pub async fn setup_services(pool: &PgPool, count: usize) -> Result<Vec<dto::Service>, dto::Error> {
     println!("Installing stock database");
 
+    let dispatcher = features::webhooks::dispatcher(pool);
     let pool = Arc::new(pool.clone());
     let repo = Arc::new(features::services::repo::Repo::new(pool));
-    let logic = Arc::new(features::services::logic::Logic::new(repo));
+    let logic = Arc::new(features::services::logic::Logic::new(repo, dispatcher));
     let mut arr = Vec::<dto::Service>::new();
 
     for i in 1..=count {
@@ -19,3 +25,175 @@ pub async fn setup_services(pool: &PgPool, count: usize) -> Result<Vec<dto::Serv
 
     Ok(arr)
 }
+
+pub struct Fixture {
+    pub first_service: i64,
+    pub second_service: i64,
+    pub first_employee: i64,
+    pub second_employee: i64,
+}
+
+pub async fn setup_requests(pool: &PgPool) -> Fixture {
+    println!("Installing stock requests");
+
+    let first_service: i64 =
+        sqlx::query_scalar("INSERT INTO service (name) VALUES ('Лендинг') RETURNING id")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    let second_service: i64 =
+        sqlx::query_scalar("INSERT INTO service (name) VALUES ('Интернет-магазин') RETURNING id")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+
+    let mut employees = Vec::<i64>::new();
+    for (name, email) in [("Иван", "ivan@mds.ru"), ("Пётр", "petr@mds.ru")] {
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO employee (name, last_name, email, password, role, active)
+            VALUES ($1, 'Иванов', $2, 'hash', 0, TRUE) RETURNING id",
+        )
+        .bind(name)
+        .bind(email)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        employees.push(id);
+    }
+    let (first_employee, second_employee) = (employees[0], employees[1]);
+
+    // (услуга, исполнитель, создана, закрыта, желаемый срок)
+    let requests = [
+        (
+            first_service,
+            None,
+            "2025-10-01 10:00:00+00",
+            Some("2025-10-01 12:00:00+00"),
+            "2025-10-02 00:00:00+00",
+        ),
+        (
+            first_service,
+            None,
+            "2025-10-01 10:00:00+00",
+            Some("2025-10-03 10:00:00+00"),
+            "2025-10-02 00:00:00+00",
+        ),
+        (
+            second_service,
+            Some(first_employee),
+            "2025-10-02 09:00:00+00",
+            None,
+            "2025-10-05 00:00:00+00",
+        ),
+        (
+            first_service,
+            Some(first_employee),
+            "2025-10-02 09:00:00+00",
+            None,
+            "2099-01-01 00:00:00+00",
+        ),
+        (
+            second_service,
+            Some(second_employee),
+            "2025-10-03 09:00:00+00",
+            Some("2025-10-03 10:00:00+00"),
+            "2025-10-04 00:00:00+00",
+        ),
+    ];
+    for (service_id, employee_id, created_at, closed_at, desired_at) in requests {
+        sqlx::query(
+            "INSERT INTO request (name, service_id, owner_id, employee_id, priority, \"desc\", status, created_at, closed_at, desired_at)
+            VALUES ('Заявка', $1::bigint, $2, $3, 0, '', 0, $4::timestamptz, $5::timestamptz, $6::timestamptz)",
+        )
+        .bind(service_id)
+        .bind(second_employee)
+        .bind(employee_id)
+        .bind(created_at)
+        .bind(closed_at)
+        .bind(desired_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    Fixture {
+        first_service,
+        second_service,
+        first_employee,
+        second_employee,
+    }
+}
+
+/// Сотрудник с ролью `role` и открытой сессией. Возвращает access токен и слой с
+/// `Authenticator`, который его принимает, для маршрутов с `AuthEmployee`.
+pub async fn authorize(
+    pool: &PgPool,
+    email: &str,
+    role: dto::Role,
+) -> (Extension<Arc<Authenticator>>, String) {
+    let id: i64 = sqlx::query_scalar(
+        "INSERT INTO employee (name, last_name, email, password, role, active)
+        VALUES ('Админ', 'Админов', $1, 'hash', $2, TRUE) RETURNING id",
+    )
+    .bind(email)
+    .bind(role)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    let session: i64 = sqlx::query_scalar(
+        "INSERT INTO session (employee_id, expires_at)
+        VALUES ($1, now() + interval '1 hour') RETURNING id",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    let keys = Arc::new(Keys::new("test-secret-test-secret-test-secret"));
+    let token = keys.access_token(id, role, session).unwrap();
+    (Extension(Arc::new(Authenticator::new(pool, keys))), token)
+}
+
+/// API ключ интеграции с правами `permissions` и слой с `Authenticator`, который его
+/// принимает, для маршрутов с `Principal`.
+pub async fn api_key(
+    pool: &PgPool,
+    permissions: &[&str],
+) -> (Extension<Arc<Authenticator>>, String) {
+    let key = format!("mds_test_{}", permissions.join("_").replace(':', "_"));
+    sqlx::query(
+        "INSERT INTO api_key (name, prefix, key_hash, permissions)
+        VALUES ('test', $1, $2, $3)",
+    )
+    .bind(&key[..12])
+    .bind(digest(&key))
+    .bind(permissions)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let keys = Arc::new(Keys::new("test-secret-test-secret-test-secret"));
+    (Extension(Arc::new(Authenticator::new(pool, keys))), key)
+}
+
+/// Сервер, который на каждый запрос отправляет API ключ с правом `reports:read`.
+pub async fn reports_server(pool: &PgPool, router: axum::Router) -> axum_test::TestServer {
+    let (authenticator, key) = api_key(pool, &["reports:read"]).await;
+    let mut server = axum_test::TestServer::new(router.layer(authenticator)).unwrap();
+    server.add_header("x-api-key", key);
+    server
+}
+
+/// Подписка в обход API: получатель в тестах слушает loopback, а API такие адреса
+/// не принимает.
+pub async fn subscribe(pool: &PgPool, url: &str, secret: &str, events: &[&str]) -> i64 {
+    sqlx::query_scalar(
+        "INSERT INTO webhook (url, secret, events, active) VALUES ($1, $2, $3, TRUE) RETURNING id",
+    )
+    .bind(url)
+    .bind(secret)
+    .bind(events)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
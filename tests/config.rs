@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use mds_backend_rust::config::{Config, MailTransport, PasswordAlgorithm, Profile, RateLimitStore};
+use mds_backend_rust::i18n::Locale;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+const FILE: &str = r#"
+profile = "prod"
+
+[server]
+ip = "0.0.0.0"
+port = 8080
+
+[database]
+user = "postgres"
+password = "admin"
+host = "db"
+port = 5432
+name = "mds"
+max_connections = 20
+
+[http]
+cors_allowed_origins = ["https://mds.example.com", "http://localhost:5173"]
+"#;
+
+#[test]
+fn test_config_file_with_env_override() {
+    println!("Testing config file layered under env");
+
+    let config = Config::from_sources(
+        Some(FILE),
+        env(&[
+            ("SERVER_PORT", "3000"),
+            ("POSTGRES_ARGS", "sslmode=disable"),
+        ]),
+    )
+    .unwrap();
+
+    assert_eq!(config.profile, Profile::Prod);
+    assert_eq!(config.server.addr.to_string(), "0.0.0.0:3000");
+    assert_eq!(config.server.shutdown_timeout, Duration::from_secs(30));
+    assert_eq!(
+        config.database.url,
+        "postgres://postgres:admin@db:5432/mds?sslmode=disable"
+    );
+    assert_eq!(config.database.max_connections, 20);
+    assert!(!config.features.swagger_ui);
+    assert_eq!(config.security.bcrypt_cost, 14);
+    assert_eq!(config.telemetry.otlp_endpoint, None);
+    assert_eq!(config.security.lockout_threshold, 5);
+    assert_eq!(
+        config.security.password_algorithm,
+        PasswordAlgorithm::Bcrypt
+    );
+    assert_eq!(config.security.password_min_length, 8);
+    assert_eq!(config.security.password_min_classes, 3);
+    assert_eq!(config.security.password_denylist, None);
+    assert_eq!(
+        config.security.password_reset_ttl,
+        Duration::from_secs(3600)
+    );
+    assert_eq!(
+        config.security.refresh_token_ttl,
+        Duration::from_secs(30 * 24 * 3600)
+    );
+    assert!(!config.security.require_2fa);
+    assert_eq!(config.security.totp_issuer, "MDS");
+    assert_eq!(config.http.default_locale, Locale::En);
+    assert_eq!(config.mail.transport, MailTransport::Log);
+    assert_eq!(config.mail.from, "noreply@localhost");
+    assert_eq!(config.rate_limit.store, RateLimitStore::Memory);
+    assert_eq!(config.rate_limit.per_ip, 20);
+    assert_eq!(config.telemetry.service_name, "mds_backend_rust");
+    assert_eq!(
+        config.http.cors_allowed_origins,
+        vec!["https://mds.example.com", "http://localhost:5173"]
+    );
+}
+
+#[test]
+fn test_config_reports_every_problem() {
+    println!("Testing config validation report");
+
+    let err = Config::from_sources(
+        None,
+        env(&[
+            ("SERVER_IP", "localhost"),
+            ("POSTGRES_PORT", "abc"),
+            ("BCRYPT_COST", "40"),
+            ("JWT_SECRET", "short"),
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "collector:4318"),
+            ("RATE_LIMIT_STORE", "redis"),
+            ("PASSWORD_MIN_CLASSES", "5"),
+            ("MAIL_TRANSPORT", "smtp"),
+            ("DEFAULT_LOCALE", "de"),
+        ]),
+    )
+    .unwrap_err();
+    println!("{err}");
+
+    let problems = err.problems.join("\n");
+    for expected in [
+        "server.ip (SERVER_IP): invalid value 'localhost'",
+        "server.port (SERVER_PORT): missing",
+        "database.user (POSTGRES_USER): missing",
+        "database.port (POSTGRES_PORT): invalid value 'abc'",
+        "security.bcrypt_cost (BCRYPT_COST): must be between 4 and 31",
+        "security.jwt_secret (JWT_SECRET): must be at least 32 characters long",
+        "telemetry.otlp_endpoint (OTEL_EXPORTER_OTLP_ENDPOINT): invalid endpoint 'collector:4318'",
+        "rate_limit.store (RATE_LIMIT_STORE): invalid value 'redis'",
+        "security.password_min_classes (PASSWORD_MIN_CLASSES): must be between 1 and 4",
+        "mail.smtp_url (SMTP_URL): required for mail.transport = smtp",
+        "http.default_locale (DEFAULT_LOCALE): invalid value 'de'",
+    ] {
+        assert!(problems.contains(expected), "missing problem: {expected}");
+    }
+}
+
+#[test]
+fn test_config_pool_settings() {
+    println!("Testing database pool settings");
+
+    let config = Config::from_sources(Some(FILE), env(&[])).unwrap();
+    assert_eq!(config.database.acquire_timeout, Duration::from_secs(30));
+    assert_eq!(config.database.idle_timeout, Some(Duration::from_secs(600)));
+    assert_eq!(
+        config.database.max_lifetime,
+        Some(Duration::from_secs(1800))
+    );
+    assert_eq!(
+        config.database.statement_timeout,
+        Some(Duration::from_millis(30_000))
+    );
+
+    let config = Config::from_sources(
+        Some(FILE),
+        env(&[
+            ("DB_MAX_CONNECTIONS", ""),
+            ("DB_IDLE_TIMEOUT", "0"),
+            ("DB_MAX_LIFETIME", "0"),
+            ("DB_STATEMENT_TIMEOUT", "0"),
+        ]),
+    )
+    .unwrap();
+    let cores = std::thread::available_parallelism().unwrap().get() as u32;
+    assert_eq!(config.database.max_connections, cores * 2 + 2);
+    assert_eq!(config.database.idle_timeout, None);
+    assert_eq!(config.database.max_lifetime, None);
+    assert_eq!(config.database.statement_timeout, None);
+}
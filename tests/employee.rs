@@ -1,5 +1,10 @@
-use axum::http::StatusCode;
-use mds_backend_rust::{features, logger};
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::{StatusCode, header};
+use mds_backend_rust::features::employee::{logic::Logic, repo::Repo};
+use mds_backend_rust::{features, i18n, logger, models::dto};
 use serde_json::json;
 use sqlx::PgPool;
 
@@ -8,24 +13,241 @@ async fn test_employee_create(pool: PgPool) {
     println!("Testing create employee");
     logger::init_dev_logger();
 
-    let app = features::employee::new(&pool);
+    let (authenticator, token) =
+        common::authorize(&pool, "admin@mds.ru", dto::Role::Superadmin).await;
+    let (_, employee_token) = common::authorize(&pool, "ivan@mds.ru", dto::Role::Employee).await;
+    let app = features::employee::new(&pool).layer(authenticator);
     let server = axum_test::TestServer::new(app).unwrap();
 
     let payload = json!({
         "name": "Василий",
         "last_name": "Ломоносов",
         "email": "shagin.v.i.21@gmail.com",
-        "password": "qwerty",
-        "role": "Сотрудник",
+        "password": "Qwerty123",
+        "role": "employee",
     });
 
-    // Request 1 - OK
+    // Only superadmin creates employees
     let response = server.post("/employee").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let mut escalation = payload.clone();
+    escalation["role"] = json!("superadmin");
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&employee_token)
+        .json(&escalation)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    // Weak password is rejected by the policy
+    let mut weak = payload.clone();
+    weak["password"] = json!("qwerty");
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&weak)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Request 1 - OK
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&payload)
+        .await;
 
     assert_eq!(response.status_code(), StatusCode::CREATED);
 
     // Request 2 - Error
-    let response = server.post("/employee").json(&payload).await;
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&payload)
+        .await;
 
     assert_eq!(response.status_code(), StatusCode::CONFLICT);
 }
+
+#[sqlx::test]
+async fn test_employee_roles(pool: PgPool) {
+    println!("Testing role codes and display names");
+    logger::init_dev_logger();
+
+    let (authenticator, token) =
+        common::authorize(&pool, "admin@mds.ru", dto::Role::Superadmin).await;
+    let app = features::employee::new(&pool)
+        .layer(axum::middleware::from_fn(i18n::negotiate))
+        .layer(authenticator);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/employee/roles").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let roles: serde_json::Value = response.json();
+    assert_eq!(
+        roles,
+        json!([
+            { "code": "employee", "name": "Employee" },
+            { "code": "manager", "name": "Manager" },
+            { "code": "superadmin", "name": "Superadmin" },
+        ])
+    );
+
+    // Names follow Accept-Language, codes stay the same
+    let response = server
+        .get("/employee/roles")
+        .add_header(header::ACCEPT_LANGUAGE, "ru")
+        .await;
+    let roles: serde_json::Value = response.json();
+    assert_eq!(
+        roles,
+        json!([
+            { "code": "employee", "name": "Сотрудник" },
+            { "code": "manager", "name": "Менеджер" },
+            { "code": "superadmin", "name": "Суперадмин" },
+        ])
+    );
+
+    let mut payload = json!({
+        "name": "Василий",
+        "last_name": "Ломоносов",
+        "email": "v@mds.ru",
+        "password": "Qwerty123",
+        "role": "director",
+    });
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&payload)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let body: dto::ErrorResponse = response.json();
+    assert_eq!(body.error, "Unknown role: director");
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .add_header(header::ACCEPT_LANGUAGE, "ru")
+        .json(&payload)
+        .await;
+    let body: dto::ErrorResponse = response.json();
+    assert_eq!(body.error, "Неизвестная роль: director");
+
+    // Old display names are still accepted
+    payload["role"] = json!("Менеджер");
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&payload)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let role: i32 = sqlx::query_scalar("SELECT role FROM employee WHERE email = 'v@mds.ru'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(role, 1);
+}
+
+#[sqlx::test]
+async fn test_employee_admin_operations(pool: PgPool) {
+    println!("Testing admin operations on employees");
+    logger::init_dev_logger();
+
+    let (authenticator, token) =
+        common::authorize(&pool, "root@example.com", dto::Role::Superadmin).await;
+    let app = features::employee::new(&pool).layer(authenticator);
+    let server = axum_test::TestServer::new(app).unwrap();
+    let logic = Logic::new(Arc::new(Repo::new(Arc::new(pool.clone()))));
+
+    let email = "admin@example.com";
+    let payload = json!({
+        "name": "Анна",
+        "last_name": "Иванова",
+        "email": email,
+        "password": "Qwerty123",
+        "role": "superadmin",
+    });
+    let response = server
+        .post("/employee")
+        .authorization_bearer(&token)
+        .json(&payload)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+
+    // List
+    let employees = logic.get_employees().await.unwrap();
+    assert_eq!(employees.len(), 2);
+    let created = employees
+        .iter()
+        .find(|x| x.email.as_deref() == Some(email))
+        .unwrap();
+    assert_eq!(created.role.as_deref(), Some("superadmin"));
+    assert_eq!(created.active, Some(true));
+    assert_eq!(created.password, None);
+
+    // Unlock
+    let lock = "UPDATE employee SET failed_logins = 3, locked_at = CURRENT_TIMESTAMP
+        WHERE email = $1 RETURNING id";
+    sqlx::query(lock).bind(email).execute(&pool).await.unwrap();
+    logic.unlock_employee_by_email(email).await.unwrap();
+    let locked: (i32, bool) = sqlx::query_as(
+        "SELECT failed_logins, locked_at IS NOT NULL FROM employee WHERE email = $1",
+    )
+    .bind(email)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(locked, (0, false));
+    assert!(matches!(
+        logic.unlock_employee_by_email("missing@example.com").await,
+        Err(dto::Error::NotFound(_))
+    ));
+
+    // Reset password also unlocks and ends sessions
+    let id: i64 = sqlx::query_scalar(lock)
+        .bind(email)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO session (employee_id, expires_at) VALUES ($1, now() + interval '1 hour')",
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    logic.reset_password(email, "New-password1").await.unwrap();
+    let (hash, locked): (String, bool) =
+        sqlx::query_as("SELECT password, locked_at IS NOT NULL FROM employee WHERE email = $1")
+            .bind(email)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(bcrypt::verify("New-password1", &hash).unwrap());
+    assert!(!locked);
+    let active: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM session WHERE employee_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(active, 0);
+    assert!(matches!(
+        logic
+            .reset_password("missing@example.com", "New-password1")
+            .await,
+        Err(dto::Error::NotFound(_))
+    ));
+
+    // Deactivate
+    logic.deactivate_employee(email).await.unwrap();
+    let employees = logic.get_employees().await.unwrap();
+    let created = employees
+        .iter()
+        .find(|x| x.email.as_deref() == Some(email))
+        .unwrap();
+    assert_eq!(created.active, Some(false));
+    assert!(matches!(
+        logic.deactivate_employee("missing@example.com").await,
+        Err(dto::Error::NotFound(_))
+    ));
+}
@@ -0,0 +1,256 @@
+mod common;
+
+use axum::http::{StatusCode, header};
+use mds_backend_rust::{features, logger, models::dto};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_export_requests_csv(pool: PgPool) {
+    println!("Testing export requests to CSV");
+    logger::init_dev_logger();
+
+    let fixture = common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::export::new(&pool)).await;
+
+    // Request 1 - all requests
+    let response = server.get("/export/requests").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(
+        response.header(header::CONTENT_TYPE),
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.header(header::CONTENT_DISPOSITION),
+        "attachment; filename=\"requests.csv\""
+    );
+
+    let bytes = response.as_bytes();
+    assert!(bytes.starts_with(b"\xEF\xBB\xBF"));
+    let text = String::from_utf8(bytes[3..].to_vec()).unwrap();
+    println!("Result request:\n{}", text);
+
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "ID,Название,Услуга,Автор,Исполнитель,Приоритет,Статус,Создана,Желаемый срок,Закрыта"
+    );
+    assert_eq!(lines.len(), 6);
+    assert_eq!(
+        lines[3],
+        "3,Заявка,Интернет-магазин,Иванов Пётр,Иванов Иван,0,0,2025-10-02 09:00:00,2025-10-05 00:00:00,"
+    );
+
+    // Request 2 - filtered by service and date range
+    let response = server
+        .get(
+            format!(
+                "/export/requests?format=csv&service_id={}&from=2025-10-02",
+                fixture.first_service
+            )
+            .as_str(),
+        )
+        .await;
+    let text = response.text();
+    assert_eq!(text.lines().count(), 2);
+
+    // Request 3 - invalid range
+    let response = server
+        .get("/export/requests?from=2025-10-03&to=2025-10-01")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Request 4 - unknown format
+    let response = server.get("/export/requests?format=pdf").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_export_xlsx(pool: PgPool) {
+    println!("Testing export to XLSX");
+    logger::init_dev_logger();
+
+    common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::export::new(&pool)).await;
+
+    for path in [
+        "/export/requests?format=xlsx",
+        "/export/services?format=xlsx",
+    ] {
+        let response = server.get(path).await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.header(header::CONTENT_TYPE),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        // XLSX - это zip-архив
+        assert!(response.as_bytes().starts_with(b"PK\x03\x04"));
+    }
+}
+
+#[sqlx::test]
+async fn test_export_services_csv(pool: PgPool) {
+    println!("Testing export services to CSV");
+    logger::init_dev_logger();
+
+    let services = common::setup_services(&pool, 3)
+        .await
+        .expect("Failed to created services");
+    let server = common::reports_server(&pool, features::export::new(&pool)).await;
+
+    let response = server.get("/export/services").await;
+    let text = response.text();
+    println!("Result request:\n{}", text);
+
+    let names: Vec<String> = text
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').nth(1).unwrap().to_string())
+        .collect();
+    assert_eq!(
+        names,
+        services.into_iter().map(|x| x.name).collect::<Vec<_>>()
+    );
+}
+
+#[sqlx::test]
+async fn test_export_services_filter(pool: PgPool) {
+    println!("Testing export services by category subtree");
+    logger::init_dev_logger();
+
+    let root: i64 = sqlx::query_scalar("INSERT INTO category (name) VALUES ('Web') RETURNING id")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let child: i64 = sqlx::query_scalar(
+        "INSERT INTO category (name, parent_id) VALUES ('Shops', $1) RETURNING id",
+    )
+    .bind(root)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    for (name, category) in [
+        ("Landing", Some(root)),
+        ("Store", Some(child)),
+        ("Audit", None),
+    ] {
+        sqlx::query("INSERT INTO service (name, category_id) VALUES ($1, $2)")
+            .bind(name)
+            .bind(category)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let server = common::reports_server(&pool, features::export::new(&pool)).await;
+
+    let names = |text: String| -> Vec<String> {
+        text.lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(1).unwrap().to_string())
+            .collect()
+    };
+    let response = server
+        .get("/export/services")
+        .add_query_param("category_id", root)
+        .await;
+    assert_eq!(names(response.text()), vec!["Landing", "Store"]);
+    let response = server
+        .get("/export/services")
+        .add_query_param("category_id", child)
+        .await;
+    assert_eq!(names(response.text()), vec!["Store"]);
+}
+
+#[sqlx::test]
+async fn test_export_services_metadata(pool: PgPool) {
+    println!("Testing export of service metadata and active filter");
+    logger::init_dev_logger();
+
+    sqlx::query(
+        "INSERT INTO service (name, description, base_price, currency, duration_minutes,
+            skill_level, active)
+        VALUES ('Landing', 'Одна страница', 1500000, 'RUB', 2400, 'middle', TRUE),
+            ('Legacy', NULL, NULL, NULL, NULL, NULL, FALSE)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let server = common::reports_server(&pool, features::export::new(&pool)).await;
+
+    let response = server.get("/export/services").await;
+    let text = response.text();
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next().unwrap().trim_start_matches('\u{feff}'),
+        "ID,Название,Описание,\"Цена, мин. единиц валюты\",Валюта,\"Длительность, мин\",Уровень,Активна,Создана,Изменена"
+    );
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(
+        rows[0][1..8],
+        [
+            "Landing",
+            "Одна страница",
+            "1500000",
+            "RUB",
+            "2400",
+            "middle",
+            "да"
+        ]
+    );
+    assert_eq!(rows[1][1..8], ["Legacy", "", "", "", "", "", "нет"]);
+
+    let response = server
+        .get("/export/services")
+        .add_query_param("active", false)
+        .await;
+    let names: Vec<String> = response
+        .text()
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').nth(1).unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["Legacy"]);
+}
+
+#[sqlx::test]
+async fn test_export_access(pool: PgPool) {
+    println!("Testing export access by API key and employee role");
+    logger::init_dev_logger();
+
+    let (authenticator, key) = common::api_key(&pool, &["reports:read"]).await;
+    let (_, other_key) = common::api_key(&pool, &["requests:create"]).await;
+    let (_, manager) = common::authorize(&pool, "manager@mds.ru", dto::Role::Manager).await;
+    let (_, employee) = common::authorize(&pool, "ivan@mds.ru", dto::Role::Employee).await;
+    let server =
+        axum_test::TestServer::new(features::export::new(&pool).layer(authenticator)).unwrap();
+
+    let response = server.get("/export/services").await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = server
+        .get("/export/services")
+        .add_header("x-api-key", "mds_unknown")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Key scope and employee role both decide
+    let response = server
+        .get("/export/services")
+        .add_header("x-api-key", &other_key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    let response = server
+        .get("/export/services")
+        .authorization_bearer(&employee)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    for request in [
+        server.get("/export/services").add_header("x-api-key", &key),
+        server.get("/export/services").authorization_bearer(&key),
+        server
+            .get("/export/services")
+            .authorization_bearer(&manager),
+    ] {
+        let response = request.await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+}
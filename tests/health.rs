@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use mds_backend_rust::{
+    features, logger,
+    models::dto::{HealthStatus, Liveness, Readiness},
+};
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_live(pool: PgPool) {
+    println!("Testing liveness");
+    logger::init_dev_logger();
+
+    let app = features::health::new(&pool);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/health/live").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Liveness = response.json();
+    assert_eq!(body.status, HealthStatus::Up);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_ready(pool: PgPool) {
+    println!("Testing readiness");
+    logger::init_dev_logger();
+
+    let app = features::health::new(&pool);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/health/ready").await;
+    let body: Readiness = response.json();
+    println!("Result request:\n{:?}", body);
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(body.status, HealthStatus::Up);
+    assert_eq!(body.database.status, HealthStatus::Up);
+    assert!(body.migrations.pending.is_empty());
+    assert!(body.pool.saturation < 1.0);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_ready_without_migrations(pool: PgPool) {
+    println!("Testing readiness without migrations");
+    logger::init_dev_logger();
+
+    let app = features::health::new(&pool);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/health/ready").await;
+    let body: Readiness = response.json();
+    println!("Result request:\n{:?}", body);
+
+    assert_eq!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body.status, HealthStatus::Down);
+    assert_eq!(body.database.status, HealthStatus::Up);
+    assert_eq!(body.migrations.status, HealthStatus::Down);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_ready_with_saturated_pool(pool: PgPool) {
+    println!("Testing readiness with a saturated but reachable pool");
+    logger::init_dev_logger();
+
+    let small = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with((*pool.connect_options()).clone())
+        .await
+        .unwrap();
+    let app = features::health::new(&small);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    // Единственное соединение занято и освобождается, пока проверка ждёт в очереди
+    let connection = small.acquire().await.unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        drop(connection);
+    });
+
+    let response = server.get("/health/ready").await;
+    let body: Readiness = response.json();
+    println!("Result request:\n{:?}", body);
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(body.pool.saturation, 1.0);
+    assert_eq!(body.pool.status, HealthStatus::Up);
+}
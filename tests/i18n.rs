@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use axum::http::{StatusCode, header};
+use axum::{Json, Router, routing::get};
+use mds_backend_rust::i18n::{self, Locale};
+use mds_backend_rust::{logger, models::dto};
+
+/// Маршрут с ошибкой из шаблона, чтобы проверить перевод с подстановкой.
+async fn failing() -> Result<StatusCode, (StatusCode, Json<dto::ErrorResponse>)> {
+    Err(
+        dto::Error::BadRequest("Password must be at least 12 characters long.".to_string())
+            .into_response(),
+    )
+}
+
+fn catalog(source: &str) -> HashMap<String, String> {
+    toml::from_str(source).unwrap()
+}
+
+#[test]
+fn test_catalogs_have_same_messages() {
+    println!("Testing that every message is translated to every locale");
+
+    let en = catalog(include_str!("../locales/en.toml"));
+    let ru = catalog(include_str!("../locales/ru.toml"));
+    let mut missing: Vec<&String> = en.keys().filter(|key| !ru.contains_key(*key)).collect();
+    missing.extend(ru.keys().filter(|key| !en.contains_key(*key)));
+    assert!(missing.is_empty(), "not in both catalogs: {missing:?}");
+
+    for (key, translation) in &ru {
+        assert_eq!(
+            key.matches("{}").count(),
+            translation.matches("{}").count(),
+            "placeholders differ for {key:?}"
+        );
+    }
+}
+
+#[test]
+fn test_translate_and_negotiate() {
+    println!("Testing message translation and Accept-Language negotiation");
+
+    assert_eq!(
+        Locale::Ru.translate("Invalid email or password"),
+        "Неверный email или пароль"
+    );
+    assert_eq!(
+        Locale::Ru.translate("Service with id: 42 not found"),
+        "Услуга с id 42 не найдена"
+    );
+    assert_eq!(
+        Locale::Ru.translate("Password must contain at least 3 of: lowercase letters, uppercase letters, digits, symbols."),
+        "Пароль должен содержать хотя бы 3 из: строчные буквы, заглавные буквы, цифры, символы."
+    );
+    // Unknown messages are left as is
+    assert_eq!(Locale::Ru.translate("Something else"), "Something else");
+    assert_eq!(
+        Locale::En.format("Unknown role: {}", &[&"director"]),
+        "Unknown role: director"
+    );
+
+    assert_eq!(
+        Locale::negotiate("ru-RU,ru;q=0.9,en;q=0.8"),
+        Some(Locale::Ru)
+    );
+    assert_eq!(
+        Locale::negotiate("de-DE, en;q=0.5, ru;q=0.7"),
+        Some(Locale::Ru)
+    );
+    assert_eq!(Locale::negotiate("EN-us"), Some(Locale::En));
+    assert_eq!(Locale::negotiate("ru;q=0, en"), Some(Locale::En));
+    assert_eq!(Locale::negotiate("de, fr"), None);
+    assert_eq!(Locale::negotiate(""), None);
+}
+
+#[tokio::test]
+async fn test_error_messages_follow_accept_language() {
+    println!("Testing localized error responses");
+    logger::init_dev_logger();
+
+    let app = Router::new()
+        .route("/failing", get(failing))
+        .layer(axum::middleware::from_fn(i18n::negotiate));
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/failing").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.header(header::CONTENT_LANGUAGE), "en");
+    let error: dto::ErrorResponse = response.json();
+    assert_eq!(error.error, "Password must be at least 12 characters long.");
+
+    let response = server
+        .get("/failing")
+        .add_header(header::ACCEPT_LANGUAGE, "ru-RU,ru;q=0.9")
+        .await;
+    assert_eq!(response.header(header::CONTENT_LANGUAGE), "ru");
+    let error: dto::ErrorResponse = response.json();
+    assert_eq!(error.error, "Пароль должен быть не короче 12 символов.");
+}
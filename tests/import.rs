@@ -0,0 +1,177 @@
+mod common;
+
+use axum::http::StatusCode;
+use mds_backend_rust::{features, logger, models::dto};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_import_services(pool: PgPool) {
+    println!("Testing import services");
+    logger::init_dev_logger();
+
+    let server = axum_test::TestServer::new(features::import::new(&pool)).unwrap();
+    let services = axum_test::TestServer::new(features::services::new(&pool)).unwrap();
+
+    // Request 1 - invalid rows reject the whole file
+    let csv = "name\nЛендинг\n\nИнтернет-магазин\nЛендинг\n";
+    let response = server.post("/import/services").text(csv).await;
+    let report: dto::ImportReport = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&report).expect("Failed to format JSON")
+    );
+
+    assert_eq!(response.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!((report.total, report.valid, report.imported), (3, 2, 0));
+    assert_eq!(
+        report.errors,
+        vec![dto::ImportRowError {
+            row: 5,
+            error: "Object already exists.".to_string()
+        }]
+    );
+    assert!(
+        services
+            .get("/services")
+            .await
+            .json::<Vec<dto::Service>>()
+            .is_empty()
+    );
+
+    // Request 2 - dry run of a valid file writes nothing
+    let csv = "\u{feff}name\nЛендинг\nИнтернет-магазин\n";
+    let response = server
+        .post("/import/services")
+        .add_query_param("dry_run", true)
+        .text(csv)
+        .await;
+    let report: dto::ImportReport = response.json();
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!((report.total, report.valid, report.imported), (2, 2, 0));
+    assert!(
+        services
+            .get("/services")
+            .await
+            .json::<Vec<dto::Service>>()
+            .is_empty()
+    );
+
+    // Request 3 - import
+    let response = server.post("/import/services").text(csv).await;
+    let report: dto::ImportReport = response.json();
+
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    assert_eq!(report.imported, 2);
+    let names: Vec<String> = services
+        .get("/services")
+        .await
+        .json::<Vec<dto::Service>>()
+        .into_iter()
+        .map(|x| x.name)
+        .collect();
+    assert_eq!(names, vec!["Лендинг", "Интернет-магазин"]);
+
+    // Request 4 - a database failure aborts the import instead of becoming a row error
+    sqlx::query(
+        "CREATE FUNCTION fail_service() RETURNS trigger AS $$
+        BEGIN RAISE EXCEPTION 'storage failure'; END $$ LANGUAGE plpgsql",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "CREATE TRIGGER fail_service BEFORE INSERT ON service
+        FOR EACH ROW WHEN (NEW.name = 'Сбой') EXECUTE FUNCTION fail_service()",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let response = server
+        .post("/import/services")
+        .text("name\nАудит\nСбой\n")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        services
+            .get("/services")
+            .await
+            .json::<Vec<dto::Service>>()
+            .len(),
+        2
+    );
+}
+
+#[sqlx::test]
+async fn test_import_employees(pool: PgPool) {
+    println!("Testing import employees");
+    logger::init_dev_logger();
+
+    let (authenticator, token) =
+        common::authorize(&pool, "admin@mds.ru", dto::Role::Superadmin).await;
+    let (_, manager_token) = common::authorize(&pool, "manager@mds.ru", dto::Role::Manager).await;
+    let server =
+        axum_test::TestServer::new(features::import::new(&pool).layer(authenticator)).unwrap();
+
+    let csv = "name,last_name,middle_name,email,password,role
+Василий,Ломоносов,,v@mds.ru,Qwerty123,employee
+Пётр,Петров,Петрович,p@mds.ru,Qwerty123,director
+Иван,,,i@mds.ru,Qwerty123,manager
+Анна,Смирнова,,v@mds.ru,Qwerty123,manager
+";
+
+    // Only superadmin imports employees
+    let response = server.post("/import/employees").text(csv).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = server
+        .post("/import/employees")
+        .authorization_bearer(&manager_token)
+        .text(csv)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    // Request 1 - dry run reports every invalid row
+    let response = server
+        .post("/import/employees")
+        .authorization_bearer(&token)
+        .add_query_param("dry_run", true)
+        .text(csv)
+        .await;
+    let report: dto::ImportReport = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&report).expect("Failed to format JSON")
+    );
+
+    assert_eq!(response.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!((report.total, report.valid), (4, 1));
+    let errors: Vec<(usize, String)> = report
+        .errors
+        .into_iter()
+        .map(|x| (x.row, x.error))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (3, "Unknown role: director".to_string()),
+            (4, "Some fields are empty".to_string()),
+            (5, "Employee already is exists".to_string()),
+        ]
+    );
+
+    // Request 2 - valid file
+    let csv = "name,last_name,email,password,role\nВасилий,Ломоносов,v@mds.ru,Qwerty123,employee\n";
+    let response = server
+        .post("/import/employees")
+        .authorization_bearer(&token)
+        .text(csv)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+
+    let password: String =
+        sqlx::query_scalar("SELECT password FROM employee WHERE email = 'v@mds.ru'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(bcrypt::verify("Qwerty123", &password).unwrap());
+}
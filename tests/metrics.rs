@@ -0,0 +1,50 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{Router, http::StatusCode};
+use mds_backend_rust::i18n::Locale;
+use mds_backend_rust::{config::HttpConfig, features, logger, middleware};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_metrics(pool: PgPool) {
+    println!("Testing Prometheus metrics");
+    logger::init_dev_logger();
+    common::setup_requests(&pool).await;
+
+    let router = Router::new()
+        .merge(features::services::new(&pool))
+        .merge(features::metrics::new(&pool));
+    let config = HttpConfig {
+        request_timeout: Duration::from_secs(30),
+        body_limit: 1024 * 1024,
+        cors_allowed_origins: Vec::new(),
+        compression: false,
+        default_locale: Locale::En,
+    };
+    let server = axum_test::TestServer::new(middleware::apply(router, &config)).unwrap();
+
+    let response = server.get("/services").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = server.get("/services/100500").await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = server.get("/metrics").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body = response.text();
+    println!("Result request:\n{}", body);
+
+    for expected in [
+        r#"http_requests_total{method="GET",route="/services",status="200"} 1"#,
+        r#"http_requests_total{method="GET",route="/services/{id}",status="404"} 1"#,
+        r#"http_request_duration_seconds_bucket{method="GET",route="/services",status="200",le="+Inf"} 1"#,
+        r#"db_query_duration_seconds_count{repo="services",method="get_all_services"} 1"#,
+        r#"db_pool_connections{pool="primary",state="idle"}"#,
+        r#"db_pool_max_connections{pool="primary"}"#,
+        "db_pool_waiters ",
+        r#"requests_open{status="0",priority="0"} 2"#,
+    ] {
+        assert!(body.contains(expected), "missing metric: {expected}");
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use axum::{
+    Router,
+    http::{HeaderValue, StatusCode},
+    routing::{get, post},
+};
+use mds_backend_rust::i18n::Locale;
+use mds_backend_rust::{config::HttpConfig, logger, middleware};
+
+fn app() -> axum_test::TestServer {
+    let router = Router::new()
+        .route("/text", get(|| async { "Заявка ".repeat(200) }))
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done"
+            }),
+        )
+        .route("/echo", post(|body: String| async move { body }));
+    let config = HttpConfig {
+        request_timeout: Duration::from_millis(200),
+        body_limit: 1024,
+        cors_allowed_origins: vec!["https://mds.example.com".to_string()],
+        compression: true,
+        default_locale: Locale::En,
+    };
+    axum_test::TestServer::new(middleware::apply(router, &config)).unwrap()
+}
+
+#[tokio::test]
+async fn test_request_id() {
+    println!("Testing request id generation and propagation");
+    logger::init_dev_logger();
+    let server = app();
+
+    // Generated
+    let response = server.get("/text").await;
+    let id = response.header(middleware::REQUEST_ID_HEADER);
+    assert_eq!(id.len(), 36);
+
+    // Propagated from the client
+    let response = server
+        .get("/text")
+        .add_header(middleware::REQUEST_ID_HEADER, "client-id-42")
+        .await;
+    assert_eq!(
+        response.header(middleware::REQUEST_ID_HEADER),
+        "client-id-42"
+    );
+}
+
+#[tokio::test]
+async fn test_timeout_and_body_limit() {
+    println!("Testing request timeout and body limit");
+    logger::init_dev_logger();
+    let server = app();
+
+    let response = server.get("/slow").await;
+    assert_eq!(response.status_code(), StatusCode::REQUEST_TIMEOUT);
+
+    let response = server.post("/echo").text("x".repeat(100)).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let response = server.post("/echo").text("x".repeat(2048)).await;
+    assert_eq!(response.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_cors_and_compression() {
+    println!("Testing CORS allowlist and compression");
+    logger::init_dev_logger();
+    let server = app();
+
+    // Allowed origin
+    let response = server
+        .get("/text")
+        .add_header("origin", "https://mds.example.com")
+        .await;
+    assert_eq!(
+        response.header("access-control-allow-origin"),
+        HeaderValue::from_static("https://mds.example.com")
+    );
+
+    // Unknown origin
+    let response = server
+        .get("/text")
+        .add_header("origin", "https://evil.example.com")
+        .await;
+    assert!(
+        response
+            .maybe_header("access-control-allow-origin")
+            .is_none()
+    );
+
+    // Compression
+    let response = server
+        .get("/text")
+        .add_header("accept-encoding", "gzip")
+        .await;
+    assert_eq!(response.header("content-encoding"), "gzip");
+    let response = server.get("/text").await;
+    assert!(response.maybe_header("content-encoding").is_none());
+}
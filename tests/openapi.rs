@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use mds_backend_rust::{logger, openapi};
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_openapi_document() {
+    println!("Testing OpenAPI document");
+    logger::init_dev_logger();
+
+    let app = openapi::new(false);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    // Request
+    let response = server.get("/openapi.json").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let doc: Value = response.json();
+
+    // Check
+    assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(doc["paths"]["/services"]["post"].is_object());
+    assert!(doc["paths"]["/services/{id}"]["delete"].is_object());
+    assert!(doc["paths"]["/employee"]["post"].is_object());
+    assert!(doc["components"]["schemas"]["ErrorResponse"].is_object());
+    assert!(doc["components"]["schemas"]["Service"].is_object());
+    // Цена передаётся в минимальных единицах валюты, это видно клиентам
+    let base_price = &doc["components"]["schemas"]["Service"]["properties"]["base_price"];
+    assert!(
+        base_price["description"]
+            .as_str()
+            .unwrap()
+            .contains("минимальных единицах")
+    );
+    assert_eq!(base_price["example"], 1500000);
+    assert_eq!(
+        doc["paths"]["/services/{id}"]["get"]["responses"]["404"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/ErrorResponse"
+    );
+}
+
+#[tokio::test]
+async fn test_swagger_ui() {
+    println!("Testing Swagger UI");
+    logger::init_dev_logger();
+
+    let app = openapi::new(true);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let response = server.get("/swagger-ui/").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response.text().contains("swagger"));
+
+    let response = server.get("/openapi.json").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+}
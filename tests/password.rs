@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{Extension, http::StatusCode};
+use mds_backend_rust::config::{PasswordAlgorithm, SecurityConfig};
+use mds_backend_rust::features::auth::{Authenticator, Keys, Settings};
+use mds_backend_rust::{features, logger, password};
+use serde_json::json;
+use sqlx::PgPool;
+
+fn configure() {
+    password::configure(&SecurityConfig {
+        bcrypt_cost: 4,
+        password_algorithm: PasswordAlgorithm::Argon2id,
+        hash_concurrency: 2,
+        jwt_secret: None,
+        jwt_refresh_secret: None,
+        refresh_token_ttl: Duration::from_secs(3600),
+        require_2fa: false,
+        totp_issuer: "MDS".to_string(),
+        lockout_threshold: 5,
+        password_min_length: 8,
+        password_min_classes: 3,
+        password_denylist: Some(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/breached-passwords.txt"
+            )
+            .to_string(),
+        ),
+        password_reset_ttl: Duration::from_secs(3600),
+    })
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_hash_and_verify() {
+    println!("Testing password hashing off the async runtime");
+    configure();
+
+    let hash = password::hash("qwerty").await.unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(password::verify("qwerty", &hash).await.unwrap());
+    assert!(!password::verify("wrong", &hash).await.unwrap());
+    assert!(!password::needs_rehash(&hash));
+
+    // Old bcrypt hashes are still accepted but scheduled for rehash
+    let old = bcrypt::hash("qwerty", 4).unwrap();
+    assert!(password::verify("qwerty", &old).await.unwrap());
+    assert!(password::needs_rehash(&old));
+
+    // Concurrent hashing is bounded but completes
+    let tasks: Vec<_> = (0..8)
+        .map(|i| tokio::spawn(async move { password::hash(&format!("password-{i}")).await }))
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_password_policy() {
+    println!("Testing password policy");
+    configure();
+
+    let message = |password: &str| {
+        password::check_policy(password)
+            .unwrap_err()
+            .message()
+            .to_string()
+    };
+    assert!(message("Qw1!").contains("at least 8 characters"));
+    assert!(message("qwertyuiop").contains("at least 3 of"));
+    assert!(message("QWERTY-UIOP").contains("at least 3 of"));
+    // Denylist is case-insensitive
+    assert!(message("qWERTY123").contains("too common"));
+    assert!(message("p@ssw0rd").contains("too common"));
+
+    assert!(password::check_policy("Qwerty-uiop").is_ok());
+    assert!(password::check_policy("correct horse 7").is_ok());
+    assert!(password::check_policy("Пароль-2026").is_ok());
+}
+
+#[sqlx::test]
+async fn test_rehash_on_login(pool: PgPool) {
+    println!("Testing transparent rehash on login");
+    logger::init_dev_logger();
+    configure();
+
+    sqlx::query(
+        "INSERT INTO employee (name, last_name, email, password, role, active)
+        VALUES ('Иван', 'Петров', 'ivan@example.com', $1, 0, true)",
+    )
+    .bind(bcrypt::hash("qwerty", 4).unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let keys = Arc::new(Keys::new("test-secret-test-secret-test-secret"));
+    let app = features::auth::new(&pool, Settings::new(keys.clone()))
+        .layer(Extension(Arc::new(Authenticator::new(&pool, keys))));
+    let server = axum_test::TestServer::new(app).unwrap();
+    let payload = json!({ "email": "ivan@example.com", "password": "qwerty" });
+
+    let response = server.post("/auth/login").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let hash: String =
+        sqlx::query_scalar("SELECT password FROM employee WHERE email = 'ivan@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+
+    // The upgraded hash keeps working
+    let response = server.post("/auth/login").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use mds_backend_rust::features::employee::{logic::Logic, repo::Repo};
+use mds_backend_rust::{features, logger, models::dto};
+use sqlx::PgPool;
+
+/// Отдельная база рядом с тестовой, играющая роль реплики.
+async fn create_replica(pool: &PgPool, name: &str) -> PgPool {
+    sqlx::query(&format!("DROP DATABASE IF EXISTS {name}"))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(&format!("CREATE DATABASE {name}"))
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let options = pool.connect_options().as_ref().clone().database(name);
+    let replica = PgPool::connect_with(options).await.unwrap();
+    sqlx::migrate!().run(&replica).await.unwrap();
+    replica
+}
+
+async fn drop_replica(pool: &PgPool, replica: PgPool, name: &str) {
+    replica.close().await;
+    sqlx::query(&format!("DROP DATABASE {name}"))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test]
+async fn test_lists_are_read_from_replica(pool: PgPool) {
+    println!("Testing read replica routing");
+    logger::init_dev_logger();
+
+    let name = "mds_test_replica";
+    let replica = create_replica(&pool, name).await;
+    sqlx::query("INSERT INTO service (name) VALUES ('Только на реплике')")
+        .execute(&replica)
+        .await
+        .unwrap();
+
+    let app = features::services::with_read_pool(&pool, &replica);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    // Запись идёт в основную базу
+    let payload = dto::Service::new(None, Some("Создание сайта".to_string()));
+    let response = server.post("/services").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let created: dto::Service = response.json();
+
+    // Список читается с реплики
+    let list: Vec<dto::Service> = server.get("/services").await.json();
+    let names: Vec<_> = list.iter().map(|s| s.name.clone()).collect();
+    assert_eq!(names, vec!["Только на реплике".to_string()]);
+
+    // Чтение по id остаётся на основной базе
+    let response = server
+        .get(format!("/services/{}", created.id.unwrap()).as_str())
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Список сотрудников тоже читается с реплики
+    let repo = Repo::new(Arc::new(pool.clone())).with_read_pool(Arc::new(replica.clone()));
+    let logic = Logic::new(Arc::new(repo));
+    sqlx::query(
+        "INSERT INTO employee (name, last_name, email, password, role, active)
+        VALUES ('Иван', 'Петров', 'ivan@example.com', 'hash', 0, true)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert!(logic.get_employees().await.unwrap().is_empty());
+
+    drop_replica(&pool, replica, name).await;
+}
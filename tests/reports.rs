@@ -0,0 +1,175 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use mds_backend_rust::{features, logger, models::dto};
+use sqlx::PgPool;
+
+fn day(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+#[sqlx::test]
+async fn test_daily_volume(pool: PgPool) {
+    println!("Testing daily volume report");
+    logger::init_dev_logger();
+
+    let fixture = common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::reports::new(&pool)).await;
+
+    // Request 1 - without filters
+    let response = server.get("/reports/requests/daily").await;
+    let result: Vec<dto::DailyVolume> = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&result).expect("Failed to format JSON")
+    );
+    let rows: Vec<(NaiveDate, i64, i64)> =
+        result.iter().map(|x| (x.day, x.opened, x.closed)).collect();
+    assert_eq!(
+        rows,
+        vec![
+            (day("2025-10-01"), 2, 1),
+            (day("2025-10-02"), 2, 0),
+            (day("2025-10-03"), 1, 2)
+        ]
+    );
+
+    // Request 2 - filtered by service
+    let response = server
+        .get(
+            format!(
+                "/reports/requests/daily?service_id={}",
+                fixture.first_service
+            )
+            .as_str(),
+        )
+        .await;
+    let result: Vec<dto::DailyVolume> = response.json();
+    let rows: Vec<(NaiveDate, i64, i64)> =
+        result.iter().map(|x| (x.day, x.opened, x.closed)).collect();
+    assert_eq!(
+        rows,
+        vec![
+            (day("2025-10-01"), 2, 1),
+            (day("2025-10-02"), 1, 0),
+            (day("2025-10-03"), 0, 1)
+        ]
+    );
+
+    // Request 3 - filtered by date range
+    let response = server
+        .get("/reports/requests/daily?from=2025-10-02&to=2025-10-02")
+        .await;
+    let result: Vec<dto::DailyVolume> = response.json();
+    assert_eq!(result.len(), 1);
+    assert_eq!((result[0].opened, result[0].closed), (2, 0));
+
+    // Request 4 - invalid range
+    let response = server
+        .get("/reports/requests/daily?from=2025-10-03&to=2025-10-01")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_resolution_time(pool: PgPool) {
+    println!("Testing resolution time report");
+    logger::init_dev_logger();
+
+    let fixture = common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::reports::new(&pool)).await;
+
+    let response = server.get("/reports/resolution-time").await;
+    let result: Vec<dto::ResolutionTime> = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&result).expect("Failed to format JSON")
+    );
+
+    assert_eq!(
+        result,
+        vec![
+            dto::ResolutionTime {
+                service_id: Some(fixture.first_service),
+                service_name: Some("Лендинг".to_string()),
+                closed: 2,
+                median_seconds: 90000.0,
+                p90_seconds: 156240.0,
+            },
+            dto::ResolutionTime {
+                service_id: Some(fixture.second_service),
+                service_name: Some("Интернет-магазин".to_string()),
+                closed: 1,
+                median_seconds: 3600.0,
+                p90_seconds: 3600.0,
+            },
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn test_employee_workload(pool: PgPool) {
+    println!("Testing employee workload report");
+    logger::init_dev_logger();
+
+    let fixture = common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::reports::new(&pool)).await;
+
+    let response = server.get("/reports/workload").await;
+    let result: Vec<dto::EmployeeWorkload> = response.json();
+    let rows: Vec<(i64, i64)> = result
+        .iter()
+        .map(|x| (x.employee_id, x.open_requests))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![(fixture.first_employee, 2), (fixture.second_employee, 0)]
+    );
+
+    let response = server
+        .get(format!("/reports/workload?service_id={}", fixture.second_service).as_str())
+        .await;
+    let result: Vec<dto::EmployeeWorkload> = response.json();
+    let rows: Vec<(i64, i64)> = result
+        .iter()
+        .map(|x| (x.employee_id, x.open_requests))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![(fixture.first_employee, 1), (fixture.second_employee, 0)]
+    );
+}
+
+#[sqlx::test]
+async fn test_sla_compliance(pool: PgPool) {
+    println!("Testing SLA compliance report");
+    logger::init_dev_logger();
+
+    common::setup_requests(&pool).await;
+    let server = common::reports_server(&pool, features::reports::new(&pool)).await;
+
+    // Request 1 - all requests
+    let response = server.get("/reports/sla").await;
+    let result: dto::SlaCompliance = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&result).expect("Failed to format JSON")
+    );
+    assert_eq!(
+        result,
+        dto::SlaCompliance {
+            met: 2,
+            breached: 2,
+            pending: 1,
+            compliance_rate: Some(0.5),
+        }
+    );
+
+    // Request 2 - nothing due in range
+    let response = server
+        .get("/reports/sla?from=2030-01-01&to=2030-12-31")
+        .await;
+    let result: dto::SlaCompliance = response.json();
+    assert_eq!(result.compliance_rate, None);
+}
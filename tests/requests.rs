@@ -0,0 +1,134 @@
+mod common;
+
+use axum::http::StatusCode;
+use mds_backend_rust::{features, logger, models::dto};
+use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_create_request(pool: PgPool) {
+    println!("Testing request creation by employees and API keys");
+    logger::init_dev_logger();
+
+    let (authenticator, token) = common::authorize(&pool, "ivan@mds.ru", dto::Role::Employee).await;
+    let (_, key) = common::api_key(&pool, &["requests:create"]).await;
+    let (_, reports_key) = common::api_key(&pool, &["reports:read"]).await;
+    let server =
+        axum_test::TestServer::new(features::requests::new(&pool).layer(authenticator)).unwrap();
+    let service: i64 =
+        sqlx::query_scalar("INSERT INTO service (name) VALUES ('Хостинг') RETURNING id")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let legacy: i64 = sqlx::query_scalar(
+        "INSERT INTO service (name, active) VALUES ('Старый хостинг', FALSE) RETURNING id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let body = json!({
+        "name": "Сайт недоступен",
+        "service_id": service,
+        "priority": 2,
+        "description": "502 на главной",
+        "desired_at": "2030-01-01T00:00:00Z",
+    });
+
+    // Employee
+    let response = server
+        .post("/requests")
+        .authorization_bearer(&token)
+        .json(&body)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let request: dto::Request = response.json();
+    let employee: i64 = sqlx::query_scalar("SELECT id FROM employee WHERE email = 'ivan@mds.ru'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        (
+            request.service_id,
+            request.owner_id,
+            request.priority,
+            request.status
+        ),
+        (Some(service), Some(employee), Some(2), Some(0))
+    );
+    assert_eq!(request.description.as_deref(), Some("502 на главной"));
+
+    // API key is written on the employee who issued it
+    let response = server
+        .post("/requests")
+        .add_header("x-api-key", &key)
+        .json(&body)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    sqlx::query("UPDATE api_key SET created_by = $1")
+        .bind(employee)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = server
+        .post("/requests")
+        .add_header("x-api-key", &key)
+        .json(&json!({"name": "Диск заполнен", "desired_at": "2030-01-01T00:00:00Z"}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let request: dto::Request = response.json();
+    assert_eq!(
+        (request.service_id, request.owner_id, request.priority),
+        (None, Some(employee), Some(0))
+    );
+
+    // Scope and authentication
+    let response = server
+        .post("/requests")
+        .add_header("x-api-key", &reports_key)
+        .json(&body)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    let response = server.post("/requests").json(&body).await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Validation
+    for (payload, error) in [
+        (
+            json!({"name": " ", "desired_at": "2030-01-01T00:00:00Z"}),
+            "Field 'name' can't be empty.".to_string(),
+        ),
+        (
+            json!({"name": "Заявка"}),
+            "Field 'desired_at' is required.".to_string(),
+        ),
+        (
+            json!({"name": "Заявка", "priority": -1, "desired_at": "2030-01-01T00:00:00Z"}),
+            "Field 'priority' can't be negative.".to_string(),
+        ),
+        (
+            json!({"name": "Заявка", "service_id": legacy, "desired_at": "2030-01-01T00:00:00Z"}),
+            format!("Service with id: {legacy} is not active"),
+        ),
+        (
+            json!({"name": "Заявка", "service_id": 999, "desired_at": "2030-01-01T00:00:00Z"}),
+            "Service with id: 999 not found".to_string(),
+        ),
+    ] {
+        let response = server
+            .post("/requests")
+            .authorization_bearer(&token)
+            .json(&payload)
+            .await;
+        let body: dto::ErrorResponse = response.json();
+        assert_eq!(
+            (response.status_code(), body.error),
+            (StatusCode::BAD_REQUEST, error)
+        );
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM request")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+}
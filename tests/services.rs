@@ -143,7 +143,7 @@ async fn test_get_service_by_id(pool: PgPool) {
     let server = axum_test::TestServer::new(app).unwrap();
 
     // Request №1 - Testing get service with exists id
-    let id = 2 as i64;
+    let id = 2_i64;
     let response = server.get(format!("/services/{}", id).as_str()).await;
     let response_json = response.json::<dto::Service>();
     println!(
@@ -157,7 +157,7 @@ async fn test_get_service_by_id(pool: PgPool) {
     );
 
     // Request №2 - Testing get service with doesn't exists id
-    let id = 4 as i64;
+    let id = 4_i64;
     let response = server.get(format!("/services/{}", id).as_str()).await;
     let status_code = response.status_code();
     let response_json = response.json::<dto::ErrorResponse>();
@@ -289,3 +289,161 @@ async fn test_delete_service(pool: PgPool) {
 
     assert_eq!(response_after_delete.json::<Vec<dto::Service>>(), services);
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_service_metadata(pool: PgPool) {
+    println!("Testing service metadata");
+    logger::init_dev_logger();
+
+    let app = features::services::new(&pool);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    // Старые клиенты передают только название
+    let response = server
+        .post("/services")
+        .json(&serde_json::json!({"name": "Консультация"}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let legacy: dto::Service = response.json();
+    assert_eq!(
+        (
+            legacy.description.flatten(),
+            legacy.base_price.flatten(),
+            legacy.active
+        ),
+        (None, None, Some(true))
+    );
+
+    let payload = serde_json::json!({
+        "name": "Лендинг",
+        "description": "Одностраничный сайт",
+        "base_price": 1_500_000,
+        "currency": "RUB",
+        "duration_minutes": 2400,
+        "skill_level": "middle",
+    });
+    let response = server.post("/services").json(&payload).await;
+    let service: dto::Service = response.json();
+    println!(
+        "Result request:\n{}\n",
+        serde_json::to_string_pretty(&service).expect("Failed to format JSON")
+    );
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    assert_eq!(
+        service.description.flatten().as_deref(),
+        Some("Одностраничный сайт")
+    );
+    assert_eq!(
+        (service.base_price.flatten(), service.currency.flatten()),
+        (Some(1_500_000), Some("RUB".to_string()))
+    );
+    assert_eq!(service.duration_minutes.flatten(), Some(2400));
+    assert_eq!(service.skill_level.flatten().as_deref(), Some("middle"));
+
+    // Изменение без метаданных их не сбрасывает
+    let id = service.id.unwrap();
+    let response = server
+        .put(&format!("/services/{id}"))
+        .json(&serde_json::json!({"name": "Лендинг под ключ", "active": false}))
+        .await;
+    let updated: dto::Service = response.json();
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(
+        (
+            updated.name.as_str(),
+            updated.active,
+            updated.base_price.flatten()
+        ),
+        ("Лендинг под ключ", Some(false), Some(1_500_000))
+    );
+    assert_eq!(
+        updated.description.flatten().as_deref(),
+        Some("Одностраничный сайт")
+    );
+
+    // Явный null очищает значение, цена и валюта очищаются вместе
+    let response = server
+        .put(&format!("/services/{id}"))
+        .json(&serde_json::json!({"name": "Лендинг под ключ", "base_price": null}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let response = server
+        .put(&format!("/services/{id}"))
+        .json(&serde_json::json!({
+            "name": "Лендинг под ключ",
+            "description": null,
+            "base_price": null,
+            "currency": null,
+            "duration_minutes": null,
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let cleared: dto::Service = response.json();
+    assert_eq!(
+        (
+            cleared.description.flatten(),
+            cleared.base_price.flatten(),
+            cleared.currency.flatten(),
+            cleared.duration_minutes.flatten(),
+            cleared.skill_level.flatten().as_deref(),
+        ),
+        (None, None, None, None, Some("middle"))
+    );
+
+    let response = server
+        .get("/services")
+        .add_query_param("active", false)
+        .await;
+    let inactive: Vec<dto::Service> = response.json();
+    assert_eq!(inactive.len(), 1);
+    assert_eq!(inactive[0].id, Some(id));
+    let response = server
+        .get("/services")
+        .add_query_param("active", true)
+        .await;
+    assert_eq!(response.json::<Vec<dto::Service>>()[0].id, legacy.id);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_service_metadata_validation(pool: PgPool) {
+    println!("Testing service metadata validation");
+    logger::init_dev_logger();
+
+    let app = features::services::new(&pool);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let cases = [
+        (
+            serde_json::json!({"name": "Лендинг", "base_price": 100}),
+            "Fields 'base_price' and 'currency' must be set together.",
+        ),
+        (
+            serde_json::json!({"name": "Лендинг", "base_price": -1, "currency": "RUB"}),
+            "Field 'base_price' can't be negative.",
+        ),
+        (
+            serde_json::json!({"name": "Лендинг", "base_price": 100, "currency": "rubles"}),
+            "Field 'currency' must be an ISO 4217 code, e.g. RUB.",
+        ),
+        (
+            serde_json::json!({"name": "Лендинг", "duration_minutes": 0}),
+            "Field 'duration_minutes' must be positive.",
+        ),
+        (
+            serde_json::json!({"name": "Лендинг", "skill_level": "guru"}),
+            "Unknown skill level: guru",
+        ),
+        (
+            serde_json::json!({"name": "Лендинг", "description": "a".repeat(2001)}),
+            "Field 'description' must be at most 2000 characters.",
+        ),
+    ];
+    for (payload, error) in cases {
+        let response = server.post("/services").json(&payload).await;
+        let result_json: dto::ErrorResponse = response.json();
+        assert_eq!(
+            (response.status_code(), result_json.error),
+            (StatusCode::BAD_REQUEST, error.to_string())
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// Остановка рассылки глобальна для процесса, поэтому тест вынесен в отдельный бинарник.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use axum::{Router, http::StatusCode, routing::post};
+use mds_backend_rust::{
+    features::{self, webhooks::dispatcher},
+    logger,
+    models::{dao::WebhookEvent, dto},
+};
+use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_dispatcher_stop_abandons_retries(pool: PgPool) {
+    println!("Testing webhook dispatcher stop");
+    logger::init_dev_logger();
+
+    let app = Router::new().route(
+        "/hook",
+        post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (authenticator, key) = common::api_key(&pool, &["webhooks:manage"]).await;
+    let mut webhooks =
+        axum_test::TestServer::new(features::webhooks::new(&pool).layer(authenticator)).unwrap();
+    webhooks.add_header("x-api-key", key);
+    let id = common::subscribe(&pool, &url, "secret", &["service.deleted"]).await;
+
+    // Без остановки следующая попытка была бы только через минуту
+    let handle = features::webhooks::dispatcher(&pool)
+        .with_retry(5, Duration::from_secs(60))
+        .dispatch(WebhookEvent::ServiceDeleted, json!({"id": 1}));
+
+    let mut deliveries = Vec::<dto::WebhookDelivery>::new();
+    for _ in 0..50 {
+        deliveries = webhooks
+            .get(format!("/webhooks/{id}/deliveries").as_str())
+            .await
+            .json();
+        if !deliveries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(deliveries.len(), 1);
+
+    let started = Instant::now();
+    let unfinished = dispatcher::stop(Duration::from_secs(5)).await;
+    assert_eq!(unfinished, 0);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(handle.is_finished());
+
+    let deliveries: Vec<dto::WebhookDelivery> = webhooks
+        .get(format!("/webhooks/{id}/deliveries").as_str())
+        .await
+        .json();
+    assert_eq!(deliveries.len(), 1);
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use mds_backend_rust::i18n::Locale;
+use mds_backend_rust::{config::HttpConfig, features, logger, middleware, models::dto};
+use opentelemetry::trace::{SpanId, TraceId};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use sqlx::PgPool;
+use tracing_subscriber::prelude::*;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_ID: &str = "00f067aa0ba902b7";
+
+fn find<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|s| s.name == name)
+        .unwrap_or_else(|| panic!("missing span: {name}"))
+}
+
+#[sqlx::test]
+async fn test_trace_handler_logic_repo(pool: PgPool) {
+    println!("Testing OpenTelemetry spans and traceparent propagation");
+
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry().with(logger::otel_layer(&provider));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let config = HttpConfig {
+        request_timeout: Duration::from_secs(30),
+        body_limit: 1024 * 1024,
+        cors_allowed_origins: Vec::new(),
+        compression: false,
+        default_locale: Locale::En,
+    };
+    let app = middleware::apply(features::services::new(&pool), &config);
+    let server = axum_test::TestServer::new(app).unwrap();
+
+    let payload = dto::Service::new(None, Some("Создание сайта".to_string()));
+    let response = server
+        .post("/services")
+        .add_header("traceparent", format!("00-{TRACE_ID}-{PARENT_ID}-01"))
+        .json(&payload)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+
+    provider.force_flush().unwrap();
+    let spans = exporter.get_finished_spans().unwrap();
+    for span in &spans {
+        println!("{} {:?}", span.name, span.parent_span_id);
+    }
+
+    // Запрос продолжает трассировку клиента
+    let request = find(&spans, "request");
+    assert_eq!(
+        request.span_context.trace_id(),
+        TraceId::from_hex(TRACE_ID).unwrap()
+    );
+    assert_eq!(request.parent_span_id, SpanId::from_hex(PARENT_ID).unwrap());
+
+    // handler -> logic -> repo -> запрос к базе
+    let handler = find(&spans, "Service handler: create_service");
+    let logic = find(&spans, "Service logic: create");
+    let repo = find(&spans, "Service repo: add_service");
+    let insert = find(&spans, "Service repo: insert");
+    assert_eq!(handler.parent_span_id, request.span_context.span_id());
+    assert_eq!(logic.parent_span_id, handler.span_context.span_id());
+    assert_eq!(repo.parent_span_id, logic.span_context.span_id());
+    assert_eq!(insert.parent_span_id, repo.span_context.span_id());
+    for span in [handler, logic, repo, insert] {
+        assert_eq!(
+            span.span_context.trace_id(),
+            TraceId::from_hex(TRACE_ID).unwrap()
+        );
+        assert!(span.end_time >= span.start_time);
+    }
+}
@@ -0,0 +1,329 @@
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode, header},
+    routing::post,
+};
+use mds_backend_rust::{
+    features::{self, webhooks::dispatcher},
+    logger,
+    models::{dao::WebhookEvent, dto},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+
+struct Receiver {
+    // Сколько первых запросов ответить ошибкой 500
+    fail_first: usize,
+    calls: AtomicUsize,
+    tx: mpsc::UnboundedSender<(HeaderMap, String)>,
+}
+
+async fn receive(
+    State(receiver): State<Arc<Receiver>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let call = receiver.calls.fetch_add(1, Ordering::SeqCst);
+    let _ = receiver
+        .tx
+        .send((headers, String::from_utf8_lossy(&body).to_string()));
+    if call < receiver.fail_first {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::OK
+    }
+}
+
+async fn spawn_receiver(
+    fail_first: usize,
+) -> (String, mpsc::UnboundedReceiver<(HeaderMap, String)>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let state = Arc::new(Receiver {
+        fail_first,
+        calls: AtomicUsize::new(0),
+        tx,
+    });
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{}/hook", addr), rx)
+}
+
+#[sqlx::test]
+async fn test_webhook_crud(pool: PgPool) {
+    println!("Testing webhook subscriptions");
+    logger::init_dev_logger();
+
+    let (authenticator, token) =
+        common::authorize(&pool, "manager@mds.ru", dto::Role::Manager).await;
+    let (_, employee) = common::authorize(&pool, "ivan@mds.ru", dto::Role::Employee).await;
+    let app = features::webhooks::new(&pool).layer(authenticator);
+    let mut server = axum_test::TestServer::new(app).unwrap();
+
+    // Only managers and keys with webhooks:manage
+    let response = server.get("/webhooks").await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    let response = server
+        .get("/webhooks")
+        .authorization_bearer(&employee)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    server.add_header(header::AUTHORIZATION, format!("Bearer {token}"));
+
+    // Request 1 - invalid url
+    let payload =
+        json!({"url": "ftp://example.com", "secret": "s3cr3t", "events": ["service.created"]});
+    let response = server.post("/webhooks").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Internal hosts are rejected
+    for url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://10.0.0.5/hook",
+        "http://192.168.1.10/hook",
+        "http://[::1]/hook",
+        "http://[::ffff:172.16.0.1]/hook",
+        "http://postgres:5432",
+        "http://api.default.svc.cluster.local/hook",
+        "http://metadata.google.internal/",
+    ] {
+        let payload = json!({"url": url, "secret": "s3cr3t", "events": ["service.created"]});
+        let response = server.post("/webhooks").json(&payload).await;
+        let json_body: dto::ErrorResponse = response.json();
+        assert_eq!(
+            (response.status_code(), json_body.error),
+            (
+                StatusCode::BAD_REQUEST,
+                "Field 'url' must point to a public host.".to_string()
+            ),
+            "{url}"
+        );
+    }
+
+    // Only request creation is emitted, requests can't be changed through the API yet
+    let payload =
+        json!({"url": "http://example.com", "secret": "s3cr3t", "events": ["request.updated"]});
+    let response = server.post("/webhooks").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Request 2 - unknown event
+    let payload =
+        json!({"url": "http://example.com", "secret": "s3cr3t", "events": ["service.exploded"]});
+    let response = server.post("/webhooks").json(&payload).await;
+    let json_body: dto::ErrorResponse = response.json();
+    assert_eq!(
+        (response.status_code(), json_body.error),
+        (
+            StatusCode::BAD_REQUEST,
+            "Unknown webhook event: service.exploded".to_string()
+        )
+    );
+
+    // Request 3 - OK, secret is not returned
+    let payload = json!({"url": "http://example.com", "secret": "s3cr3t", "events": ["service.created", "service.created", "service.deleted"]});
+    let response = server.post("/webhooks").json(&payload).await;
+    let created: dto::Webhook = response.json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&created).expect("Failed to format JSON")
+    );
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    assert_eq!(created.secret, None);
+    assert_eq!(
+        created.events,
+        Some(vec![
+            "service.created".to_string(),
+            "service.deleted".to_string()
+        ])
+    );
+
+    // Request 4 - update and list
+    let id = created.id.unwrap();
+    let payload =
+        json!({"url": "https://example.com/hook", "events": ["service.deleted"], "active": false});
+    let response = server
+        .put(format!("/webhooks/{id}").as_str())
+        .json(&payload)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let response = server.get("/webhooks").await;
+    let list: Vec<dto::Webhook> = response.json();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0].url, Some("https://example.com/hook".to_string()));
+    assert_eq!(list[0].active, Some(false));
+
+    // Without active the webhook stays disabled
+    let payload = json!({"url": "https://example.com/hook", "events": ["service.created"]});
+    let response = server
+        .put(format!("/webhooks/{id}").as_str())
+        .json(&payload)
+        .await;
+    let updated: dto::Webhook = response.json();
+    assert_eq!(updated.active, Some(false));
+
+    // Request 5 - delete, then deliveries of deleted webhook are not found
+    let response = server.delete(format!("/webhooks/{id}").as_str()).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = server
+        .get(format!("/webhooks/{id}/deliveries").as_str())
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_webhook_delivery_on_service_create(pool: PgPool) {
+    println!("Testing webhook delivery on service create");
+    logger::init_dev_logger();
+
+    let (url, mut rx) = spawn_receiver(0).await;
+    let (authenticator, key) = common::api_key(&pool, &["webhooks:manage"]).await;
+    let mut webhooks =
+        axum_test::TestServer::new(features::webhooks::new(&pool).layer(authenticator)).unwrap();
+    webhooks.add_header("x-api-key", key);
+    let services = axum_test::TestServer::new(features::services::new(&pool)).unwrap();
+
+    let secret = "top-secret";
+    let id = common::subscribe(&pool, &url, secret, &["service.created"]).await;
+
+    let payload = dto::Service::new(None, Some("Создание сайта".to_string()));
+    let response = services.post("/services").json(&payload).await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("Webhook was not delivered")
+        .unwrap();
+    println!("Delivered body:\n{}", body);
+
+    assert_eq!(
+        headers.get(dispatcher::EVENT_HEADER).unwrap(),
+        "service.created"
+    );
+    assert_eq!(
+        headers.get(dispatcher::SIGNATURE_HEADER).unwrap(),
+        dispatcher::sign(secret, &body).as_str()
+    );
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["data"]["name"], "Создание сайта");
+
+    // Лог доставки пишется после ответа получателя
+    let mut deliveries = Vec::<dto::WebhookDelivery>::new();
+    for _ in 0..50 {
+        deliveries = webhooks
+            .get(format!("/webhooks/{id}/deliveries").as_str())
+            .await
+            .json();
+        if !deliveries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(deliveries.len(), 1);
+    assert!(deliveries[0].success);
+    assert_eq!(deliveries[0].status_code, Some(200));
+}
+
+#[sqlx::test]
+async fn test_webhook_delivery_on_request_create(pool: PgPool) {
+    println!("Testing webhook delivery on request create");
+    logger::init_dev_logger();
+
+    let (url, mut rx) = spawn_receiver(0).await;
+    let (authenticator, token) = common::authorize(&pool, "ivan@mds.ru", dto::Role::Employee).await;
+    let requests =
+        axum_test::TestServer::new(features::requests::new(&pool).layer(authenticator)).unwrap();
+    let secret = "top-secret";
+    common::subscribe(&pool, &url, secret, &["request.created"]).await;
+
+    let response = requests
+        .post("/requests")
+        .authorization_bearer(&token)
+        .json(&json!({"name": "Сайт недоступен", "desired_at": "2030-01-01T00:00:00Z"}))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let request: dto::Request = response.json();
+
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("Webhook was not delivered")
+        .unwrap();
+    assert_eq!(
+        headers.get(dispatcher::EVENT_HEADER).unwrap(),
+        "request.created"
+    );
+    assert_eq!(
+        headers.get(dispatcher::SIGNATURE_HEADER).unwrap(),
+        dispatcher::sign(secret, &body).as_str()
+    );
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["data"]["id"], json!(request.id));
+    assert_eq!(body["data"]["name"], "Сайт недоступен");
+}
+
+#[sqlx::test]
+async fn test_webhook_delivery_retry(pool: PgPool) {
+    println!("Testing webhook delivery retry with backoff");
+    logger::init_dev_logger();
+
+    let (url, mut rx) = spawn_receiver(2).await;
+    let (authenticator, key) = common::api_key(&pool, &["webhooks:manage"]).await;
+    let mut webhooks =
+        axum_test::TestServer::new(features::webhooks::new(&pool).layer(authenticator)).unwrap();
+    webhooks.add_header("x-api-key", key);
+
+    let id = common::subscribe(&pool, &url, "secret", &["service.deleted"]).await;
+
+    features::webhooks::dispatcher(&pool)
+        .with_retry(3, Duration::from_millis(10))
+        .dispatch(WebhookEvent::ServiceDeleted, json!({"id": 1}))
+        .await
+        .unwrap();
+
+    let mut calls = 0;
+    while rx.try_recv().is_ok() {
+        calls += 1;
+    }
+    assert_eq!(calls, 3);
+
+    let deliveries: Vec<dto::WebhookDelivery> = webhooks
+        .get(format!("/webhooks/{id}/deliveries").as_str())
+        .await
+        .json();
+    println!(
+        "Result request:\n{}",
+        serde_json::to_string_pretty(&deliveries).expect("Failed to format JSON")
+    );
+
+    let attempts: Vec<(i32, bool, Option<i32>)> = deliveries
+        .iter()
+        .map(|x| (x.attempt, x.success, x.status_code))
+        .collect();
+    assert_eq!(
+        attempts,
+        vec![
+            (3, true, Some(200)),
+            (2, false, Some(500)),
+            (1, false, Some(500))
+        ]
+    );
+}